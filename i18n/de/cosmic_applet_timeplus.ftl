@@ -1,22 +1,10 @@
 calendar = Kalender
+calendar-week = KW
 datetime-settings = Datum-, Zeit- und Kalendereinstellungen...
 
-# Weather placeholder
+# Weather
 weather = Wetter
-weather-subtitle = Wetter- und Klima-Widget
-weather-placeholder-message = Wetter- und Klimainformationsintegration! (Demnächst!)
-weather-placeholder-features = Geplante Funktionen:
-weather-feature-current = Aktuelle Wetterbedingungen
-weather-feature-temperature = Temperatur und gefühlte Temperatur
-weather-feature-forecast = Mehrtägige Vorhersage
 
-# Timer placeholder
+# Timer
 timer = Timer
-timer-subtitle = Timer-Widget
-timer-placeholder-message = Pomodoro und benutzerdefinierte Timer! (Demnächst!)
-timer-placeholder-features = Geplante Funktionen:
-timer-feature-countdown = Anpassbarer Countdown-Timer
-timer-feature-presets = Schnellvoreinstellungen (Pomodoro, kurze Pause, lange Pause)
-timer-feature-notifications = Desktop-Benachrichtigungen bei Abschluss
-timer-feature-persistent = Sitzungspersistenz über Neustarts hinweg
 
@@ -1,21 +1,148 @@
 calendar = Calendar
+calendar-week = Wk
+calendar-jump = Go
+calendar-jump-placeholder = 2026-04-18, 2026-04 or +6m
+calendar-jump-invalid = Use YYYY-MM-DD, YYYY-MM or an offset like +10d, -2w, +6m, +1y
 datetime-settings = Date, time, and calendar settings...
 
-# Weather placeholder
+# Weather
 weather = Weather
-weather-subtitle = Weather and Climate Widget
-weather-placeholder-message = Weather and climate information integration! (Coming soon!)
-weather-placeholder-features = Planned features:
-weather-feature-current = Current weather conditions
-weather-feature-temperature = Temperature and feels-like
-weather-feature-forecast = Multi-day forecast
-
-# Timer placeholder
+weather-loading = Loading…
+weather-unavailable = Unavailable
+weather-no-location = No location set
+weather-no-location-hint = Search for your city below to see the weather.
+weather-error = Could not update: { $error }
+weather-updated-minutes = { $minutes ->
+    [0] Updated just now
+    [one] Updated 1 minute ago
+   *[other] Updated { $minutes } minutes ago
+}
+weather-updated-hours = { $hours ->
+    [one] Updated 1 hour ago
+   *[other] Updated { $hours } hours ago
+}
+weather-stale = Outdated
+weather-refresh = Refresh
+weather-feels-like = Feels like
+weather-wind = Wind
+weather-humidity = Humidity
+weather-pressure = Pressure
+weather-hourly = Next 24 hours
+weather-daily = Next 7 days
+weather-sun = Sun
+weather-moon = Moon
+weather-today = Today
+weather-alert = Weather alert
+weather-location-placeholder = Search city…
+weather-location-from-timezone = From time zone
+weather-location-use-timezone = Use time zone
+weather-clear = Clear
+weather-partly-cloudy = Partly cloudy
+weather-overcast = Overcast
+weather-fog = Fog
+weather-drizzle = Drizzle
+weather-rain = Rain
+weather-freezing-rain = Freezing rain
+weather-snow = Snow
+weather-showers = Showers
+weather-thunderstorm = Thunderstorm
+weather-unknown = Unknown conditions
+
+# Sun
+sun-sunrise = Sunrise
+sun-sunset = Sunset
+sun-day-length = Day length
+sun-day-length-value = { $hours } h { $minutes } min
+sun-civil-twilight = Civil twilight
+sun-nautical-twilight = Nautical twilight
+sun-midnight-sun = Midnight sun: the sun does not set
+sun-polar-night = Polar night: the sun does not rise
+sun-twilight-all-night = All night
+sun-twilight-none = None
+
+# Moon
+moon-phase = Phase
+moon-illumination = Illumination
+moon-new = New moon
+moon-waxing-crescent = Waxing crescent
+moon-first-quarter = First quarter
+moon-waxing-gibbous = Waxing gibbous
+moon-full = Full moon
+moon-waning-gibbous = Waning gibbous
+moon-last-quarter = Last quarter
+moon-waning-crescent = Waning crescent
+
+# Events
+event-all-day = All day
+event-none = No events
+event-untitled = (No title)
+event-attendees = Attendees: { $names }
+event-no-details = No details
+agenda-title = Upcoming
+agenda-empty = { $days ->
+    [one] No events today
+   *[other] No events in the next { $days } days
+}
+caldav-sync-failed = Calendar sync failed: { $error }
+
+# Holidays
+holiday-public = Public holiday
+
+# Timer
 timer = Timer
-timer-subtitle = Timer Widget
-timer-placeholder-message = Pomodoro and custom timers! (Coming soon!)
-timer-placeholder-features = Planned features:
-timer-feature-countdown = Customizable countdown timer
-timer-feature-presets = Quick presets (Pomodoro, short break, long break)
-timer-feature-notifications = Desktop notifications when complete
-timer-feature-persistent = Session persistence across reboots
+timer-status-idle = Ready
+timer-status-running = Running
+timer-status-paused = Paused
+timer-status-finished = Time's up!
+timer-duration-placeholder = Duration (e.g. 25 or 25:00)
+timer-invalid-duration = Enter minutes, MM:SS or H:MM:SS
+timer-preset-minutes = { $minutes } min
+timer-start = Start
+timer-pause = Pause
+timer-resume = Resume
+timer-reset = Reset
+timer-finished-body = { $label } has finished.
+timer-default-label = Timer { $number }
+timer-label-placeholder = Name (optional)
+timer-empty = No timers yet
+timer-status-count = { $running } of { $total } running
+
+# Pomodoro
+timer-mode-countdown = Countdown
+timer-mode-pomodoro = Pomodoro
+pomodoro-work = Focus
+pomodoro-short-break = Short break
+pomodoro-long-break = Long break
+pomodoro-cycle = Cycle { $current } of { $total }
+pomodoro-phase-finished-body = { $finished } finished. Time for: { $next }.
+
+# Stopwatch
+timer-mode-stopwatch = Stopwatch
+stopwatch-stop = Stop
+stopwatch-lap-button = Lap
+stopwatch-lap = Lap { $number }
+stopwatch-lap-header = Lap
+stopwatch-split = Split
+stopwatch-total = Total
+
+# Alarms
+alarm = Alarm
+timer-mode-alarms = Alarms
+alarm-status = { $count } active
+alarm-empty = No alarms yet
+alarm-repeat-once = Once
+alarm-repeat-daily = Daily
+alarm-repeat-weekdays = Weekdays
+alarm-repeat-custom = Custom
+alarm-missed = Missed
+alarm-time-placeholder = HH:MM
+alarm-label-placeholder = Label
+alarm-add = Add alarm
+alarm-invalid = Enter a time as HH:MM (and pick at least one day for custom repeats).
+alarm-body = { $label } — { $time }
+alarm-missed-body = Missed while asleep: { $label } — { $time }
+
+# Notifications
+notification-snooze = Snooze 5 min
+notification-restart = Restart
+notification-skip = Skip
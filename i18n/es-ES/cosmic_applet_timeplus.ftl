@@ -1,22 +1,9 @@
 calendar = Calendario
 datetime-settings = Configuración de fecha, hora y calendario...
 
-# Weather placeholder
+# Weather
 weather = Clima
-weather-subtitle = Widget de Clima
-weather-placeholder-message = ¡Integración de información meteorológica y climática! (¡Próximamente!)
-weather-placeholder-features = Características planificadas:
-weather-feature-current = Condiciones meteorológicas actuales
-weather-feature-temperature = Temperatura y sensación térmica
-weather-feature-forecast = Pronóstico de varios días
 
-# Timer placeholder
+# Timer
 timer = Temporizador
-timer-subtitle = Widget de Temporizador
-timer-placeholder-message = ¡Pomodoro y temporizadores personalizados! (¡Próximamente!)
-timer-placeholder-features = Características planificadas:
-timer-feature-countdown = Temporizador de cuenta regresiva personalizable
-timer-feature-presets = Preajustes rápidos (Pomodoro, pausa corta, pausa larga)
-timer-feature-notifications = Notificaciones de escritorio al completarse
-timer-feature-persistent = Persistencia de sesión entre reinicios
 
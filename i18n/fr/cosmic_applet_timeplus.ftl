@@ -1,22 +1,9 @@
 calendar = Calendrier
 datetime-settings = Paramètres de date, heure et calendrier...
 
-# Weather placeholder
+# Weather
 weather = Météo
-weather-subtitle = Widget Météo et Climat
-weather-placeholder-message = Intégration des informations météorologiques et climatiques ! (Bientôt !)
-weather-placeholder-features = Fonctionnalités prévues :
-weather-feature-current = Conditions météorologiques actuelles
-weather-feature-temperature = Température et ressenti
-weather-feature-forecast = Prévisions sur plusieurs jours
 
-# Timer placeholder
+# Timer
 timer = Minuteur
-timer-subtitle = Widget Minuteur
-timer-placeholder-message = Pomodoro et minuteurs personnalisés ! (Bientôt !)
-timer-placeholder-features = Fonctionnalités prévues :
-timer-feature-countdown = Minuteur à rebours personnalisable
-timer-feature-presets = Préréglages rapides (Pomodoro, pause courte, pause longue)
-timer-feature-notifications = Notifications de bureau à la fin
-timer-feature-persistent = Persistance de session entre les redémarrages
 
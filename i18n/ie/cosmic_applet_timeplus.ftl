@@ -1,21 +1,8 @@
 calendar = Calendar
 datetime-settings = Date, time, and calendar settings...
 
-# Weather placeholder
+# Weather
 weather = Weather
-weather-subtitle = Weather and Climate Widget
-weather-placeholder-message = Weather and climate information integration! (Coming soon!)
-weather-placeholder-features = Planned features:
-weather-feature-current = Current weather conditions
-weather-feature-temperature = Temperature and feels-like
-weather-feature-forecast = Multi-day forecast
 
-# Timer placeholder
+# Timer
 timer = Timer
-timer-subtitle = Timer Widget
-timer-placeholder-message = Pomodoro and custom timers! (Coming soon!)
-timer-placeholder-features = Planned features:
-timer-feature-countdown = Customizable countdown timer
-timer-feature-presets = Quick presets (Pomodoro, short break, long break)
-timer-feature-notifications = Desktop notifications when complete
-timer-feature-persistent = Session persistence across reboots
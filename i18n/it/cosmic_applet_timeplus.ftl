@@ -1,22 +1,9 @@
 calendar = Calendario
 datetime-settings = Impostazioni di data, ora e calendario...
 
-# Weather placeholder
+# Weather
 weather = Meteo
-weather-subtitle = Widget Meteo e Clima
-weather-placeholder-message = Integrazione informazioni meteo e clima! (Prossimamente!)
-weather-placeholder-features = Funzionalità pianificate:
-weather-feature-current = Condizioni meteo attuali
-weather-feature-temperature = Temperatura e temperatura percepita
-weather-feature-forecast = Previsioni multi-giorno
 
-# Timer placeholder
+# Timer
 timer = Timer
-timer-subtitle = Widget Timer
-timer-placeholder-message = Pomodoro e timer personalizzati! (Prossimamente!)
-timer-placeholder-features = Funzionalità pianificate:
-timer-feature-countdown = Timer conto alla rovescia personalizzabile
-timer-feature-presets = Preset rapidi (Pomodoro, pausa breve, pausa lunga)
-timer-feature-notifications = Notifiche desktop al completamento
-timer-feature-persistent = Persistenza sessione tra riavvii
 
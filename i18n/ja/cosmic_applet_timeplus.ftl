@@ -1,22 +1,9 @@
 calendar = カレンダー
 datetime-settings = 日付、時刻、カレンダーの設定...
 
-# Weather placeholder
+# Weather
 weather = 天気
-weather-subtitle = 天気と気候ウィジェット
-weather-placeholder-message = 天気と気候情報の統合！（近日公開！）
-weather-placeholder-features = 予定されている機能：
-weather-feature-current = 現在の気象条件
-weather-feature-temperature = 気温と体感温度
-weather-feature-forecast = 複数日の予報
 
-# Timer placeholder
+# Timer
 timer = タイマー
-timer-subtitle = タイマーウィジェット
-timer-placeholder-message = ポモドーロとカスタムタイマー！（近日公開！）
-timer-placeholder-features = 予定されている機能：
-timer-feature-countdown = カスタマイズ可能なカウントダウンタイマー
-timer-feature-presets = クイックプリセット（ポモドーロ、短い休憩、長い休憩）
-timer-feature-notifications = 完了時のデスクトップ通知
-timer-feature-persistent = 再起動後のセッション永続化
 
@@ -1,22 +1,9 @@
 calendar = 달력
 datetime-settings = 날짜, 시간 및 달력 설정...
 
-# Weather placeholder
+# Weather
 weather = 날씨
-weather-subtitle = 날씨 및 기후 위젯
-weather-placeholder-message = 날씨 및 기후 정보 통합! (곧 출시!)
-weather-placeholder-features = 계획된 기능:
-weather-feature-current = 현재 날씨 상태
-weather-feature-temperature = 온도 및 체감 온도
-weather-feature-forecast = 여러 날 예보
 
-# Timer placeholder
+# Timer
 timer = 타이머
-timer-subtitle = 타이머 위젯
-timer-placeholder-message = 뽀모도로 및 사용자 정의 타이머! (곧 출시!)
-timer-placeholder-features = 계획된 기능:
-timer-feature-countdown = 사용자 정의 가능한 카운트다운 타이머
-timer-feature-presets = 빠른 사전 설정 (뽀모도로, 짧은 휴식, 긴 휴식)
-timer-feature-notifications = 완료 시 데스크톱 알림
-timer-feature-persistent = 재부팅 후 세션 지속성
 
@@ -1,21 +1,148 @@
 calendar = Calendário
+calendar-week = Sem.
+calendar-jump = Ir
+calendar-jump-placeholder = 2026-04-18, 2026-04 ou +6m
+calendar-jump-invalid = Use AAAA-MM-DD, AAAA-MM ou um deslocamento como +10d, -2w, +6m, +1y
 datetime-settings = Configurações de data, hora e calendário...
 
-# Weather placeholder
+# Weather
 weather = Clima
-weather-subtitle = Widget Tempo e Clima
-weather-placeholder-message = Integração com informações de tempo e clima! (Em breve!)
-weather-placeholder-features = Recursos planejados:
-weather-feature-current = Condições climáticas atuais
-weather-feature-temperature = Temperatura e sensação térmica
-weather-feature-forecast = Previsão para vários dias
-
-# Timer placeholder
+weather-loading = Carregando…
+weather-unavailable = Indisponível
+weather-no-location = Nenhuma localização definida
+weather-no-location-hint = Procure sua cidade abaixo para ver o clima.
+weather-error = Não foi possível atualizar: { $error }
+weather-updated-minutes = { $minutes ->
+    [0] Atualizado agora
+    [one] Atualizado há 1 minuto
+   *[other] Atualizado há { $minutes } minutos
+}
+weather-updated-hours = { $hours ->
+    [one] Atualizado há 1 hora
+   *[other] Atualizado há { $hours } horas
+}
+weather-stale = Desatualizado
+weather-refresh = Atualizar
+weather-feels-like = Sensação térmica
+weather-wind = Vento
+weather-humidity = Umidade
+weather-pressure = Pressão
+weather-hourly = Próximas 24 horas
+weather-daily = Próximos 7 dias
+weather-sun = Sol
+weather-moon = Lua
+weather-today = Hoje
+weather-alert = Alerta meteorológico
+weather-location-placeholder = Buscar cidade…
+weather-location-from-timezone = Pelo fuso horário
+weather-location-use-timezone = Usar fuso horário
+weather-clear = Céu limpo
+weather-partly-cloudy = Parcialmente nublado
+weather-overcast = Nublado
+weather-fog = Neblina
+weather-drizzle = Garoa
+weather-rain = Chuva
+weather-freezing-rain = Chuva congelante
+weather-snow = Neve
+weather-showers = Pancadas de chuva
+weather-thunderstorm = Tempestade
+weather-unknown = Condições desconhecidas
+
+# Sun
+sun-sunrise = Nascer do sol
+sun-sunset = Pôr do sol
+sun-day-length = Duração do dia
+sun-day-length-value = { $hours } h { $minutes } min
+sun-civil-twilight = Crepúsculo civil
+sun-nautical-twilight = Crepúsculo náutico
+sun-midnight-sun = Sol da meia-noite: o sol não se põe
+sun-polar-night = Noite polar: o sol não nasce
+sun-twilight-all-night = A noite toda
+sun-twilight-none = Nenhum
+
+# Moon
+moon-phase = Fase
+moon-illumination = Iluminação
+moon-new = Lua nova
+moon-waxing-crescent = Lua crescente
+moon-first-quarter = Quarto crescente
+moon-waxing-gibbous = Crescente gibosa
+moon-full = Lua cheia
+moon-waning-gibbous = Minguante gibosa
+moon-last-quarter = Quarto minguante
+moon-waning-crescent = Lua minguante
+
+# Events
+event-all-day = Dia inteiro
+event-none = Nenhum evento
+event-untitled = (Sem título)
+event-attendees = Participantes: { $names }
+event-no-details = Sem detalhes
+agenda-title = Próximos eventos
+agenda-empty = { $days ->
+    [one] Nenhum evento hoje
+   *[other] Nenhum evento nos próximos { $days } dias
+}
+caldav-sync-failed = Falha ao sincronizar calendários: { $error }
+
+# Holidays
+holiday-public = Feriado
+
+# Timer
 timer = Timer
-timer-subtitle = Widget de Timer
-timer-placeholder-message = Timers Pomodoro e personalizados! (Em breve!)
-timer-placeholder-features = Recursos planejados:
-timer-feature-countdown = Timer de contagem regressiva personalizável
-timer-feature-presets = Presets rápidos (Pomodoro, pausa curta, pausa longa)
-timer-feature-notifications = Notificações ao completar
-timer-feature-persistent = Persistência de sessão entre reinicializações
+timer-status-idle = Pronto
+timer-status-running = Em andamento
+timer-status-paused = Pausado
+timer-status-finished = Tempo esgotado!
+timer-duration-placeholder = Duração (ex.: 25 ou 25:00)
+timer-invalid-duration = Informe minutos, MM:SS ou H:MM:SS
+timer-preset-minutes = { $minutes } min
+timer-start = Iniciar
+timer-pause = Pausar
+timer-resume = Retomar
+timer-reset = Reiniciar
+timer-finished-body = { $label } terminou.
+timer-default-label = Timer { $number }
+timer-label-placeholder = Nome (opcional)
+timer-empty = Nenhum timer ainda
+timer-status-count = { $running } de { $total } em andamento
+
+# Pomodoro
+timer-mode-countdown = Contagem regressiva
+timer-mode-pomodoro = Pomodoro
+pomodoro-work = Foco
+pomodoro-short-break = Pausa curta
+pomodoro-long-break = Pausa longa
+pomodoro-cycle = Ciclo { $current } de { $total }
+pomodoro-phase-finished-body = { $finished } concluído. Próxima etapa: { $next }.
+
+# Stopwatch
+timer-mode-stopwatch = Cronômetro
+stopwatch-stop = Parar
+stopwatch-lap-button = Volta
+stopwatch-lap = Volta { $number }
+stopwatch-lap-header = Volta
+stopwatch-split = Parcial
+stopwatch-total = Total
+
+# Alarms
+alarm = Alarme
+timer-mode-alarms = Alarmes
+alarm-status = { $count } ativo(s)
+alarm-empty = Nenhum alarme ainda
+alarm-repeat-once = Uma vez
+alarm-repeat-daily = Diário
+alarm-repeat-weekdays = Dias úteis
+alarm-repeat-custom = Personalizado
+alarm-missed = Perdido
+alarm-time-placeholder = HH:MM
+alarm-label-placeholder = Rótulo
+alarm-add = Adicionar alarme
+alarm-invalid = Digite um horário como HH:MM (e escolha ao menos um dia na repetição personalizada).
+alarm-body = { $label } — { $time }
+alarm-missed-body = Perdido durante a suspensão: { $label } — { $time }
+
+# Notifications
+notification-snooze = Adiar 5 min
+notification-restart = Reiniciar
+notification-skip = Pular
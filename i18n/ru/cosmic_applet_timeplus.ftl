@@ -1,22 +1,9 @@
 calendar = Календарь
 datetime-settings = Настройки даты, времени и календаря...
 
-# Weather placeholder
+# Weather
 weather = Погода
-weather-subtitle = Виджет погоды и климата
-weather-placeholder-message = Интеграция информации о погоде и климате! (Скоро!)
-weather-placeholder-features = Планируемые функции:
-weather-feature-current = Текущие погодные условия
-weather-feature-temperature = Температура и ощущаемая температура
-weather-feature-forecast = Многодневный прогноз
 
-# Timer placeholder
+# Timer
 timer = Таймер
-timer-subtitle = Виджет таймера
-timer-placeholder-message = Pomodoro и пользовательские таймеры! (Скоро!)
-timer-placeholder-features = Планируемые функции:
-timer-feature-countdown = Настраиваемый таймер обратного отсчета
-timer-feature-presets = Быстрые пресеты (Pomodoro, короткий перерыв, длинный перерыв)
-timer-feature-notifications = Уведомления на рабочем столе при завершении
-timer-feature-persistent = Сохранение сеанса между перезагрузками
 
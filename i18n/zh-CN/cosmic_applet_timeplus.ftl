@@ -1,22 +1,9 @@
 calendar = 日历
 datetime-settings = 日期、时间和日历设置...
 
-# Weather placeholder
+# Weather
 weather = 天气
-weather-subtitle = 天气和气候小部件
-weather-placeholder-message = 天气和气候信息集成！（即将推出！）
-weather-placeholder-features = 计划功能：
-weather-feature-current = 当前天气状况
-weather-feature-temperature = 温度和体感温度
-weather-feature-forecast = 多日预报
 
-# Timer placeholder
+# Timer
 timer = 计时器
-timer-subtitle = 计时器小部件
-timer-placeholder-message = 番茄钟和自定义计时器！（即将推出！）
-timer-placeholder-features = 计划功能：
-timer-feature-countdown = 可自定义倒计时器
-timer-feature-presets = 快速预设（番茄钟、短休息、长休息）
-timer-feature-notifications = 完成时桌面通知
-timer-feature-persistent = 重启后会话持久化
 
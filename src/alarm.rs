@@ -0,0 +1,643 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Alarm module
+//!
+//! Wall-clock alarms ("weekdays at 07:30") for the Timer tab. Unlike
+//! countdowns, alarms are user settings: they live in `TimeAppletConfig` and
+//! are written through cosmic-config. The schedule (next firing instant of
+//! each alarm) is derived state, recomputed whenever the alarms, the timezone
+//! or the wall clock (resume from sleep) change. Alarms that come due late,
+//! typically because the machine was suspended, still fire but are flagged
+//! as missed.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{
+    DateTime, Datelike, Days, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    TimeZone, Utc, Weekday,
+};
+use cosmic::{
+    Element,
+    iced::{Alignment, Length},
+    widget::{Space, button, column, icon, row, text, text_input, toggler},
+};
+use icu::{
+    datetime::{DateTimeFormatter, DateTimeFormatterPreferences, fieldsets},
+    locale::Locale,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{fl, icons};
+
+/// Alarms firing later than this after their scheduled time are "missed"
+const MISSED_AFTER: TimeDelta = TimeDelta::minutes(2);
+
+/// Time pre-filled in the new alarm form
+const DEFAULT_ALARM_TIME: &str = "07:00";
+
+// ============================================================================
+// Alarm Definition (persisted in config)
+// ============================================================================
+
+/// When an alarm repeats
+///
+/// Custom days use the same numbering as `first_day_of_week`
+/// (0 = Sunday … 6 = Saturday).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlarmRepeat {
+    /// Fires once, then disables itself
+    Once,
+    Daily,
+    /// Monday to Friday
+    Weekdays,
+    Custom {
+        days: Vec<u8>,
+    },
+}
+
+impl AlarmRepeat {
+    /// Check if the alarm fires on the given day of the week
+    pub fn includes(&self, weekday: Weekday) -> bool {
+        match self {
+            AlarmRepeat::Once | AlarmRepeat::Daily => true,
+            AlarmRepeat::Weekdays => !matches!(weekday, Weekday::Sat | Weekday::Sun),
+            AlarmRepeat::Custom { days } => days.contains(&(weekday.num_days_from_sunday() as u8)),
+        }
+    }
+}
+
+/// A wall-clock alarm
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alarm {
+    /// Stable identifier (unique within the config)
+    pub id: u32,
+    #[serde(default)]
+    pub label: String,
+    pub hour: u32,
+    pub minute: u32,
+    pub repeat: AlarmRepeat,
+    pub enabled: bool,
+}
+
+impl Alarm {
+    /// Local time of day the alarm rings at
+    pub fn time(&self) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(self.hour, self.minute, 0)
+    }
+
+    /// Label shown to the user (falls back to a generic name)
+    pub fn display_label(&self) -> String {
+        if self.label.trim().is_empty() {
+            fl!("alarm")
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// `HH:MM` representation of the alarm time
+    pub fn time_label(&self) -> String {
+        format!("{:02}:{:02}", self.hour, self.minute)
+    }
+
+    /// Next time the alarm rings strictly after `after`, in `after`'s timezone
+    ///
+    /// Disabled alarms and custom repeats without days never ring.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+        if !self.enabled {
+            return None;
+        }
+        let time = self.time()?;
+        let timezone = after.timezone();
+        let after = after.to_utc();
+        let today = after.with_timezone(&timezone).date_naive();
+
+        // A week plus a day covers every repeat rule, even when today's
+        // occurrence has already passed
+        (0..=7)
+            .filter_map(|offset| today.checked_add_days(Days::new(offset)))
+            .filter(|date| self.repeat.includes(date.weekday()))
+            .filter_map(|date| resolve_local(&timezone, date.and_time(time)))
+            .find(|fire| *fire > after)
+    }
+}
+
+/// ID for a new alarm: past every current alarm and the stored counter, so
+/// the ID of a deleted alarm (still named by its notification) is never reused
+pub fn next_alarm_id(alarms: &[Alarm], stored: u32) -> u32 {
+    alarms
+        .iter()
+        .map(|alarm| alarm.id.saturating_add(1))
+        .fold(stored.max(1), u32::max)
+}
+
+/// Map a local date-time to an instant, coping with DST transitions
+///
+/// Times repeated by a backward transition ring on their first occurrence;
+/// times skipped by a forward transition ring once the clock has jumped.
+fn resolve_local<Tz: TimeZone>(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => Some(time.to_utc()),
+        LocalResult::None => timezone
+            .from_local_datetime(&(local + TimeDelta::hours(1)))
+            .earliest()
+            .map(|time| time.to_utc()),
+    }
+}
+
+/// Parse an alarm time typed as `H:MM` or `HH:MM`
+pub fn parse_time(input: &str) -> Option<(u32, u32)> {
+    let (hour, minute) = input.trim().split_once(':')?;
+    let hour: u32 = hour.trim().parse().ok()?;
+    let minute: u32 = minute.trim().parse().ok()?;
+    (hour < 24 && minute < 60).then_some((hour, minute))
+}
+
+// ============================================================================
+// Schedule
+// ============================================================================
+
+/// An alarm that has come due
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlarmEvent {
+    pub id: u32,
+    /// Fired late, e.g. because the system was asleep at the scheduled time
+    pub missed: bool,
+}
+
+/// Next firing instant of each alarm
+#[derive(Debug, Clone, Default)]
+pub struct AlarmSchedule {
+    next: HashMap<u32, DateTime<Utc>>,
+    /// One-off re-rings requested from a notification (kept across reschedules)
+    snoozed: HashMap<u32, DateTime<Utc>>,
+    /// Alarms whose last ring was missed, until acknowledged
+    missed: HashSet<u32>,
+}
+
+impl AlarmSchedule {
+    /// Recompute every alarm's next firing from `now`
+    ///
+    /// Called when the alarms or the timezone change, and after resuming
+    /// from sleep (the wall clock may have crossed a DST transition).
+    pub fn reschedule<Tz: TimeZone>(&mut self, alarms: &[Alarm], now: &DateTime<Tz>) {
+        self.next = alarms
+            .iter()
+            .filter_map(|alarm| Some((alarm.id, alarm.next_after(now)?)))
+            .collect();
+        self.snoozed
+            .retain(|id, _| alarms.iter().any(|alarm| alarm.id == *id));
+        self.missed
+            .retain(|id| alarms.iter().any(|alarm| alarm.id == *id));
+    }
+
+    /// Collect alarms due at `now`, advancing repeating ones to their next firing
+    pub fn due<Tz: TimeZone>(&mut self, alarms: &[Alarm], now: &DateTime<Tz>) -> Vec<AlarmEvent> {
+        let now_utc = now.to_utc();
+        let mut events = Vec::new();
+
+        for alarm in alarms {
+            let scheduled = self
+                .next
+                .get(&alarm.id)
+                .copied()
+                .filter(|at| *at <= now_utc);
+            let snoozed = self
+                .snoozed
+                .get(&alarm.id)
+                .copied()
+                .filter(|at| *at <= now_utc);
+            // Ring once even if a snooze and the regular time coincide
+            let Some(at) = scheduled.into_iter().chain(snoozed).min() else {
+                continue;
+            };
+
+            if snoozed.is_some() {
+                self.snoozed.remove(&alarm.id);
+            }
+            if scheduled.is_some() {
+                match alarm
+                    .next_after(now)
+                    .filter(|_| alarm.repeat != AlarmRepeat::Once)
+                {
+                    Some(next) => self.next.insert(alarm.id, next),
+                    None => self.next.remove(&alarm.id),
+                };
+            }
+
+            let missed = now_utc - at > MISSED_AFTER;
+            if missed {
+                self.missed.insert(alarm.id);
+            } else {
+                self.missed.remove(&alarm.id);
+            }
+            events.push(AlarmEvent {
+                id: alarm.id,
+                missed,
+            });
+        }
+        events
+    }
+
+    /// Ring the alarm again at `at`
+    pub fn snooze(&mut self, id: u32, at: DateTime<Utc>) {
+        self.snoozed.insert(id, at);
+    }
+
+    /// Check if the last ring of the alarm was missed
+    pub fn is_missed(&self, id: u32) -> bool {
+        self.missed.contains(&id)
+    }
+
+    /// Clear the missed marker (the user has seen the alarm)
+    pub fn acknowledge(&mut self, id: u32) {
+        self.missed.remove(&id);
+    }
+}
+
+// ============================================================================
+// Alarm Form
+// ============================================================================
+
+/// Repeat rule selected in the form (custom days are edited separately)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatKind {
+    Once,
+    Daily,
+    Weekdays,
+    Custom,
+}
+
+impl RepeatKind {
+    const ALL: [RepeatKind; 4] = [
+        RepeatKind::Once,
+        RepeatKind::Daily,
+        RepeatKind::Weekdays,
+        RepeatKind::Custom,
+    ];
+
+    fn label(self) -> String {
+        match self {
+            RepeatKind::Once => fl!("alarm-repeat-once"),
+            RepeatKind::Daily => fl!("alarm-repeat-daily"),
+            RepeatKind::Weekdays => fl!("alarm-repeat-weekdays"),
+            RepeatKind::Custom => fl!("alarm-repeat-custom"),
+        }
+    }
+}
+
+/// State of the "new alarm" form
+#[derive(Debug, Clone)]
+pub struct AlarmForm {
+    time: String,
+    label: String,
+    repeat: RepeatKind,
+    days: BTreeSet<u8>,
+}
+
+impl Default for AlarmForm {
+    fn default() -> Self {
+        Self {
+            time: DEFAULT_ALARM_TIME.to_string(),
+            label: String::new(),
+            repeat: RepeatKind::Once,
+            days: BTreeSet::new(),
+        }
+    }
+}
+
+impl AlarmForm {
+    /// Alarm described by the form, if it is valid
+    fn build(&self, id: u32) -> Option<Alarm> {
+        let (hour, minute) = parse_time(&self.time)?;
+        let repeat = match self.repeat {
+            RepeatKind::Once => AlarmRepeat::Once,
+            RepeatKind::Daily => AlarmRepeat::Daily,
+            RepeatKind::Weekdays => AlarmRepeat::Weekdays,
+            RepeatKind::Custom if self.days.is_empty() => return None,
+            RepeatKind::Custom => AlarmRepeat::Custom {
+                days: self.days.iter().copied().collect(),
+            },
+        };
+        Some(Alarm {
+            id,
+            label: self.label.trim().to_string(),
+            hour,
+            minute,
+            repeat,
+            enabled: true,
+        })
+    }
+
+    /// Update the form, returning the new alarm list when it has changed
+    ///
+    /// A new alarm gets `next_id`. Alarms are config, so the caller is
+    /// responsible for persisting the returned list.
+    pub fn update(
+        &mut self,
+        message: AlarmMessage,
+        alarms: &[Alarm],
+        next_id: u32,
+    ) -> Option<Vec<Alarm>> {
+        match message {
+            AlarmMessage::TimeInput(time) => self.time = time,
+            AlarmMessage::LabelInput(label) => self.label = label,
+            AlarmMessage::SelectRepeat(repeat) => self.repeat = repeat,
+            AlarmMessage::ToggleDay(day) => {
+                if !self.days.remove(&day) {
+                    self.days.insert(day);
+                }
+            }
+            AlarmMessage::Add => {
+                let alarm = self.build(next_id)?;
+                self.label.clear();
+                let mut alarms = alarms.to_vec();
+                alarms.push(alarm);
+                return Some(alarms);
+            }
+            AlarmMessage::SetEnabled(id, enabled) => {
+                let mut alarms = alarms.to_vec();
+                for alarm in alarms.iter_mut().filter(|alarm| alarm.id == id) {
+                    alarm.enabled = enabled;
+                }
+                return Some(alarms);
+            }
+            AlarmMessage::Delete(id) => {
+                return Some(
+                    alarms
+                        .iter()
+                        .filter(|alarm| alarm.id != id)
+                        .cloned()
+                        .collect(),
+                );
+            }
+        }
+        None
+    }
+}
+
+// ============================================================================
+// Alarm Messages
+// ============================================================================
+
+/// Messages for alarm interactions
+#[derive(Debug, Clone)]
+pub enum AlarmMessage {
+    TimeInput(String),
+    LabelInput(String),
+    SelectRepeat(RepeatKind),
+    /// Add or remove a day (0 = Sunday) from the custom repeat set
+    ToggleDay(u8),
+    Add,
+    SetEnabled(u32, bool),
+    Delete(u32),
+}
+
+// ============================================================================
+// View Helpers
+// ============================================================================
+
+/// Localized short weekday names, indexed from Sunday
+fn weekday_names(locale: &Locale) -> Vec<String> {
+    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    let formatter = DateTimeFormatter::try_new(prefs, fieldsets::E::short()).unwrap();
+    // 2023-01-01 was a Sunday
+    let sunday = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    sunday
+        .iter_days()
+        .take(7)
+        .map(|date| {
+            let datetime = crate::calendar::create_datetime(&date, &NaiveTime::MIN);
+            formatter.format(&datetime).to_string()
+        })
+        .collect()
+}
+
+/// Human-readable repeat rule
+fn repeat_label(repeat: &AlarmRepeat, weekdays: &[String]) -> String {
+    match repeat {
+        AlarmRepeat::Once => fl!("alarm-repeat-once"),
+        AlarmRepeat::Daily => fl!("alarm-repeat-daily"),
+        AlarmRepeat::Weekdays => fl!("alarm-repeat-weekdays"),
+        AlarmRepeat::Custom { days } => days
+            .iter()
+            .filter_map(|day| weekdays.get(usize::from(*day)))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// One row of the alarm list
+fn alarm_row<'a>(
+    alarm: &Alarm,
+    schedule: &AlarmSchedule,
+    weekdays: &[String],
+) -> Element<'a, AlarmMessage> {
+    let mut details = vec![alarm.display_label(), repeat_label(&alarm.repeat, weekdays)];
+    if schedule.is_missed(alarm.id) {
+        details.push(fl!("alarm-missed"));
+    }
+
+    let id = alarm.id;
+    row()
+        .push(
+            column()
+                .push(text::heading(alarm.time_label()))
+                .push(text::caption(details.join(" · "))),
+        )
+        .push(Space::with_width(Length::Fill))
+        .push(
+            toggler(alarm.enabled).on_toggle(move |enabled| AlarmMessage::SetEnabled(id, enabled)),
+        )
+        .push(
+            button::icon(icon::from_name(icons::timer::DELETE))
+                .padding(8)
+                .on_press(AlarmMessage::Delete(id)),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+}
+
+/// Form adding a new alarm
+fn alarm_form<'a>(
+    form: &'a AlarmForm,
+    weekdays: &[String],
+    first_day_of_week: u8,
+) -> Element<'a, AlarmMessage> {
+    let mut repeat = row().spacing(8);
+    for kind in RepeatKind::ALL {
+        repeat = repeat.push(if kind == form.repeat {
+            button::suggested(kind.label())
+        } else {
+            button::text(kind.label()).on_press(AlarmMessage::SelectRepeat(kind))
+        });
+    }
+
+    let mut content = column()
+        .push(
+            row()
+                .push(
+                    text_input(fl!("alarm-time-placeholder"), form.time.as_str())
+                        .on_input(AlarmMessage::TimeInput)
+                        .width(Length::Fixed(80.0)),
+                )
+                .push(
+                    text_input(fl!("alarm-label-placeholder"), form.label.as_str())
+                        .on_input(AlarmMessage::LabelInput)
+                        .width(Length::Fill),
+                )
+                .spacing(8),
+        )
+        .push(repeat)
+        .spacing(8);
+
+    if form.repeat == RepeatKind::Custom {
+        let mut days = row().spacing(4);
+        for offset in 0..7 {
+            let day = (first_day_of_week % 7 + offset) % 7;
+            let name = weekdays.get(usize::from(day)).cloned().unwrap_or_default();
+            days = days.push(if form.days.contains(&day) {
+                button::suggested(name).on_press(AlarmMessage::ToggleDay(day))
+            } else {
+                button::text(name).on_press(AlarmMessage::ToggleDay(day))
+            });
+        }
+        content = content.push(days);
+    }
+
+    let valid = form.build(0).is_some();
+    content = content.push(
+        button::suggested(fl!("alarm-add")).on_press_maybe(valid.then_some(AlarmMessage::Add)),
+    );
+    if !valid {
+        content = content.push(text::caption(fl!("alarm-invalid")));
+    }
+
+    content.into()
+}
+
+// ============================================================================
+// Public View Function
+// ============================================================================
+
+/// Renders the Alarms mode: alarm list with toggles, then the new alarm form
+pub fn view_alarms<'a>(
+    locale: &Locale,
+    form: &'a AlarmForm,
+    alarms: &[Alarm],
+    schedule: &AlarmSchedule,
+    first_day_of_week: u8,
+) -> Element<'a, AlarmMessage> {
+    let weekdays = weekday_names(locale);
+
+    let mut list = column().spacing(8);
+    if alarms.is_empty() {
+        list = list.push(text::body(fl!("alarm-empty")));
+    }
+    for alarm in alarms {
+        list = list.push(alarm_row(alarm, schedule, &weekdays));
+    }
+
+    column()
+        .push(list)
+        .push(alarm_form(form, &weekdays, first_day_of_week))
+        .spacing(16)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    fn alarm(repeat: AlarmRepeat) -> Alarm {
+        Alarm {
+            id: 1,
+            label: String::new(),
+            hour: 7,
+            minute: 30,
+            repeat,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_weekdays_skip_weekend() {
+        // Friday 2024-03-08 08:00, after that day's alarm
+        let friday = Berlin.with_ymd_and_hms(2024, 3, 8, 8, 0, 0).unwrap();
+        let next = alarm(AlarmRepeat::Weekdays).next_after(&friday).unwrap();
+        assert_eq!(
+            next,
+            Berlin.with_ymd_and_hms(2024, 3, 11, 7, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dst_gap_rings_after_jump() {
+        // 02:30 does not exist in Berlin on 2024-03-31
+        let mut alarm = alarm(AlarmRepeat::Daily);
+        alarm.hour = 2;
+        let evening = Berlin.with_ymd_and_hms(2024, 3, 30, 22, 0, 0).unwrap();
+        let next = alarm.next_after(&evening).unwrap();
+        assert_eq!(
+            next,
+            Berlin.with_ymd_and_hms(2024, 3, 31, 3, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_late_ring_is_missed() {
+        let alarms = [alarm(AlarmRepeat::Daily), {
+            let mut once = alarm(AlarmRepeat::Once);
+            once.id = 2;
+            once
+        }];
+        let mut schedule = AlarmSchedule::default();
+        schedule.reschedule(
+            &alarms,
+            &Berlin.with_ymd_and_hms(2024, 3, 8, 6, 0, 0).unwrap(),
+        );
+
+        // Resumed from sleep at 09:00
+        let resumed = Berlin.with_ymd_and_hms(2024, 3, 8, 9, 0, 0).unwrap();
+        let events = schedule.due(&alarms, &resumed);
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.missed));
+        assert!(schedule.is_missed(1));
+
+        // Only the repeating alarm comes back, on time, the next morning
+        let tomorrow = Berlin.with_ymd_and_hms(2024, 3, 9, 7, 30, 0).unwrap();
+        assert!(
+            schedule
+                .due(&alarms, &(tomorrow - TimeDelta::seconds(1)))
+                .is_empty()
+        );
+        assert_eq!(
+            schedule.due(&alarms, &tomorrow),
+            [AlarmEvent {
+                id: 1,
+                missed: false
+            }]
+        );
+        assert!(!schedule.is_missed(1));
+    }
+
+    #[test]
+    fn test_deleted_alarm_id_is_not_reused() {
+        let mut form = AlarmForm::default();
+        let added = form.update(AlarmMessage::Add, &[], 1).unwrap();
+        assert_eq!(added[0].id, 1);
+        let stored = next_alarm_id(&added, 1);
+
+        // Deleting the newest alarm doesn't hand its ID out again
+        let deleted = form
+            .update(AlarmMessage::Delete(1), &added, stored)
+            .unwrap();
+        let stored = next_alarm_id(&deleted, stored);
+        let added = form.update(AlarmMessage::Add, &deleted, stored).unwrap();
+        assert_eq!(added[0].id, 2);
+    }
+}
@@ -0,0 +1,503 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Astronomy module
+//!
+//! Sunrise, sunset, twilight and day length computed locally with the
+//! sunrise equation (accurate to about a minute away from the poles), so no
+//! network request is needed. Used by the Weather tab for today and by the
+//! calendar for the selected day, at the weather location.
+//!
+//! Moon phases are computed the same way: the current phase and illumination
+//! in the Weather tab, and the new/quarter/full moon days in the calendar
+//! grid (`show_moon_phases`).
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use cosmic::{
+    Element,
+    iced::Length,
+    widget::{column, row, text},
+};
+use icu::{
+    datetime::{
+        DateTimeFormatter, DateTimeFormatterPreferences, fieldsets, options::TimePrecision,
+    },
+    locale::Locale,
+};
+
+use crate::calendar::create_datetime;
+use crate::fl;
+use crate::weather_provider::Coordinates;
+
+/// Julian date of 2000-01-01 12:00 UTC (J2000 epoch)
+const J2000: f64 = 2_451_545.0;
+
+/// Julian date of the Unix epoch
+const JULIAN_UNIX_EPOCH: f64 = 2_440_587.5;
+
+/// Axial tilt of the Earth (degrees)
+const OBLIQUITY: f64 = 23.4397;
+
+/// Sun elevation at sunrise/sunset: refraction plus the solar disc radius
+const SUNRISE_ELEVATION: f64 = -0.833;
+
+/// Sun elevation bounding civil twilight
+const CIVIL_ELEVATION: f64 = -6.0;
+
+/// Sun elevation bounding nautical twilight
+const NAUTICAL_ELEVATION: f64 = -12.0;
+
+// ============================================================================
+// Sun Times
+// ============================================================================
+
+/// When the sun crosses an elevation during a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Crossing {
+    /// Rises above the elevation, then sets below it
+    RiseSet {
+        rise: DateTime<FixedOffset>,
+        set: DateTime<FixedOffset>,
+    },
+    /// Above it the whole day (midnight sun, white nights)
+    AlwaysAbove,
+    /// Below it the whole day (polar night)
+    AlwaysBelow,
+}
+
+impl Crossing {
+    /// Time spent above the elevation
+    fn duration(self) -> TimeDelta {
+        match self {
+            Crossing::RiseSet { rise, set } => set - rise,
+            Crossing::AlwaysAbove => TimeDelta::days(1),
+            Crossing::AlwaysBelow => TimeDelta::zero(),
+        }
+    }
+}
+
+/// Sun events of one day at one place, in the display timezone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimes {
+    /// Sunrise and sunset
+    pub daylight: Crossing,
+    /// Civil dawn and dusk
+    pub civil: Crossing,
+    /// Nautical dawn and dusk
+    pub nautical: Crossing,
+}
+
+impl SunTimes {
+    /// Sun events on `date` at `location`, expressed in `tz`
+    pub fn compute<Tz: TimeZone>(date: NaiveDate, location: Coordinates, tz: &Tz) -> Self {
+        let days = (date - NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default()).num_days();
+        let solar = SolarDay::new(days as f64, location);
+        let crossing = |elevation| solar.crossing(elevation, tz);
+        Self {
+            daylight: crossing(SUNRISE_ELEVATION),
+            civil: crossing(CIVIL_ELEVATION),
+            nautical: crossing(NAUTICAL_ELEVATION),
+        }
+    }
+
+    /// Time between sunrise and sunset
+    pub fn day_length(&self) -> TimeDelta {
+        self.daylight.duration()
+    }
+}
+
+/// Solar position terms for one day at one place
+struct SolarDay {
+    /// Julian date of solar noon
+    transit: f64,
+    /// Sun declination (radians)
+    declination: f64,
+    /// Latitude (radians)
+    latitude: f64,
+}
+
+impl SolarDay {
+    /// Terms for the day `days` after 2000-01-01 at `location`
+    fn new(days: f64, location: Coordinates) -> Self {
+        let mean_solar_noon = days + 0.0008 - location.longitude / 360.0;
+        let (anomaly_rad, ecliptic_longitude) = sun_anomaly_and_longitude(mean_solar_noon);
+        let transit = J2000 + mean_solar_noon + 0.0053 * anomaly_rad.sin()
+            - 0.0069 * (2.0 * ecliptic_longitude).sin();
+        let declination = (ecliptic_longitude.sin() * OBLIQUITY.to_radians().sin()).asin();
+
+        Self {
+            transit,
+            declination,
+            latitude: location.latitude.to_radians(),
+        }
+    }
+
+    /// When the sun crosses `elevation` (degrees), in `tz`
+    fn crossing<Tz: TimeZone>(&self, elevation: f64, tz: &Tz) -> Crossing {
+        let cos_hour_angle = (elevation.to_radians().sin()
+            - self.latitude.sin() * self.declination.sin())
+            / (self.latitude.cos() * self.declination.cos());
+        if cos_hour_angle < -1.0 {
+            return Crossing::AlwaysAbove;
+        }
+        if cos_hour_angle > 1.0 {
+            return Crossing::AlwaysBelow;
+        }
+
+        let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+        match (
+            julian_to_datetime(self.transit - half_day, tz),
+            julian_to_datetime(self.transit + half_day, tz),
+        ) {
+            (Some(rise), Some(set)) => Crossing::RiseSet { rise, set },
+            // Only for dates chrono cannot represent
+            _ => Crossing::AlwaysBelow,
+        }
+    }
+}
+
+/// Sun mean anomaly and ecliptic longitude (radians), `days` after J2000
+fn sun_anomaly_and_longitude(days: f64) -> (f64, f64) {
+    let anomaly = (357.5291 + 0.985_600_28 * days).rem_euclid(360.0);
+    let anomaly_rad = anomaly.to_radians();
+    let center = 1.9148 * anomaly_rad.sin()
+        + 0.02 * (2.0 * anomaly_rad).sin()
+        + 0.0003 * (3.0 * anomaly_rad).sin();
+    let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+    (anomaly_rad, ecliptic_longitude)
+}
+
+fn julian_to_datetime<Tz: TimeZone>(julian: f64, tz: &Tz) -> Option<DateTime<FixedOffset>> {
+    let millis = ((julian - JULIAN_UNIX_EPOCH) * 86_400_000.0).round() as i64;
+    DateTime::<Utc>::from_timestamp_millis(millis).map(|utc| utc.with_timezone(tz).fixed_offset())
+}
+
+// ============================================================================
+// Moon
+// ============================================================================
+
+/// Phase of the moon, by eighths of the lunar cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    /// Phases in cycle order, starting at new moon
+    const CYCLE: [MoonPhase; 8] = [
+        MoonPhase::New,
+        MoonPhase::WaxingCrescent,
+        MoonPhase::FirstQuarter,
+        MoonPhase::WaxingGibbous,
+        MoonPhase::Full,
+        MoonPhase::WaningGibbous,
+        MoonPhase::LastQuarter,
+        MoonPhase::WaningCrescent,
+    ];
+
+    /// Text glyph of the phase; crescents and gibbous phases share the glyph
+    /// of the quarter they lie next to (as seen from the northern hemisphere)
+    pub fn glyph(self) -> &'static str {
+        match self {
+            MoonPhase::New => "●",
+            MoonPhase::WaxingCrescent | MoonPhase::FirstQuarter | MoonPhase::WaxingGibbous => "◐",
+            MoonPhase::Full => "○",
+            MoonPhase::WaningGibbous | MoonPhase::LastQuarter | MoonPhase::WaningCrescent => "◑",
+        }
+    }
+
+    /// Translated phase name
+    pub fn label(self) -> String {
+        match self {
+            MoonPhase::New => fl!("moon-new"),
+            MoonPhase::WaxingCrescent => fl!("moon-waxing-crescent"),
+            MoonPhase::FirstQuarter => fl!("moon-first-quarter"),
+            MoonPhase::WaxingGibbous => fl!("moon-waxing-gibbous"),
+            MoonPhase::Full => fl!("moon-full"),
+            MoonPhase::WaningGibbous => fl!("moon-waning-gibbous"),
+            MoonPhase::LastQuarter => fl!("moon-last-quarter"),
+            MoonPhase::WaningCrescent => fl!("moon-waning-crescent"),
+        }
+    }
+}
+
+/// Position of the moon relative to the sun at one instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moon {
+    /// Ecliptic longitude of the moon minus the sun's (degrees, 0..360):
+    /// 0 at new moon, 90 at first quarter, 180 at full moon
+    elongation: f64,
+}
+
+impl Moon {
+    /// Moon at `instant`, from the main periodic terms of its longitude
+    /// (accurate to well under an hour for the phase times)
+    pub fn at(instant: DateTime<Utc>) -> Self {
+        let days = instant.timestamp_millis() as f64 / 86_400_000.0 + JULIAN_UNIX_EPOCH - J2000;
+        let (sun_anomaly, sun_longitude) = sun_anomaly_and_longitude(days);
+
+        let mean_longitude = 218.316_447_7 + 13.176_396_48 * days;
+        let d = (297.850_192_1 + 12.190_749_12 * days).to_radians();
+        let m = (134.963_396_4 + 13.064_992_95 * days).to_radians();
+        let f = (93.272_095 + 13.229_350_24 * days).to_radians();
+        let longitude = mean_longitude
+            + 6.288_774 * m.sin()
+            + 1.274_027 * (2.0 * d - m).sin()
+            + 0.658_314 * (2.0 * d).sin()
+            + 0.213_618 * (2.0 * m).sin()
+            - 0.185_116 * sun_anomaly.sin()
+            - 0.114_332 * (2.0 * f).sin()
+            + 0.058_793 * (2.0 * d - 2.0 * m).sin()
+            + 0.057_066 * (2.0 * d - sun_anomaly - m).sin()
+            + 0.053_322 * (2.0 * d + m).sin()
+            + 0.045_758 * (2.0 * d - sun_anomaly).sin();
+
+        Self {
+            elongation: (longitude - sun_longitude.to_degrees()).rem_euclid(360.0),
+        }
+    }
+
+    /// Illuminated fraction of the disc (0 at new moon, 1 at full moon)
+    pub fn illumination(&self) -> f64 {
+        (1.0 - self.elongation.to_radians().cos()) / 2.0
+    }
+
+    /// Nearest eighth of the lunar cycle
+    pub fn phase(&self) -> MoonPhase {
+        let eighth = ((self.elongation + 22.5) / 45.0) as usize % 8;
+        MoonPhase::CYCLE[eighth]
+    }
+}
+
+/// New moon, quarter or full moon falling on `date` in `tz`, if any
+///
+/// Exactly one of these phases happens every 7.4 days on average, so most
+/// dates have none.
+pub fn principal_phase<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> Option<MoonPhase> {
+    let day_start = |date: NaiveDate| {
+        date.and_time(NaiveTime::MIN)
+            .and_local_timezone(tz.clone())
+            .earliest()
+            .map(|start| Moon::at(start.to_utc()).elongation)
+    };
+    let start = day_start(date)?;
+    let span = (day_start(date.succ_opt()?)? - start).rem_euclid(360.0);
+
+    // Quarter k is reached at elongation k × 90° (the moon gains ~12° a day)
+    (0..4)
+        .find(|quarter| (f64::from(*quarter) * 90.0 - start).rem_euclid(360.0) < span)
+        .map(|quarter| MoonPhase::CYCLE[quarter as usize * 2])
+}
+
+// ============================================================================
+// View Helpers
+// ============================================================================
+
+/// Day length as "14 h 05 min"
+fn format_day_length(length: TimeDelta) -> String {
+    let minutes = length.num_minutes();
+    fl!(
+        "sun-day-length-value",
+        hours = minutes / 60,
+        minutes = format!("{:02}", minutes % 60)
+    )
+}
+
+/// Label / value line (same layout as the weather details)
+fn detail_row<'a, M: 'a>(label: String, value: String) -> Element<'a, M> {
+    row()
+        .push(text::body(label).width(Length::Fill))
+        .push(text::body(value))
+        .into()
+}
+
+/// Sunrise, sunset, day length and twilight lines
+pub fn view_sun_times<'a, M: 'a>(locale: &Locale, sun: &SunTimes) -> Element<'a, M> {
+    // Create formatter once for this render (following cosmic-applet-time pattern)
+    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    let time_formatter = DateTimeFormatter::try_new(
+        prefs,
+        fieldsets::T::medium().with_time_precision(TimePrecision::Minute),
+    )
+    .unwrap();
+    let format_time = |time: &DateTime<FixedOffset>| {
+        time_formatter
+            .format(&create_datetime(time, time))
+            .to_string()
+    };
+    let twilight = |crossing: Crossing| match crossing {
+        Crossing::RiseSet { rise, set } => {
+            format!("{} – {}", format_time(&rise), format_time(&set))
+        }
+        Crossing::AlwaysAbove => fl!("sun-twilight-all-night"),
+        Crossing::AlwaysBelow => fl!("sun-twilight-none"),
+    };
+
+    let mut lines = column().spacing(8);
+    lines = match sun.daylight {
+        Crossing::RiseSet { rise, set } => lines
+            .push(detail_row(fl!("sun-sunrise"), format_time(&rise)))
+            .push(detail_row(fl!("sun-sunset"), format_time(&set))),
+        Crossing::AlwaysAbove => lines.push(text::body(fl!("sun-midnight-sun"))),
+        Crossing::AlwaysBelow => lines.push(text::body(fl!("sun-polar-night"))),
+    };
+    lines
+        .push(detail_row(
+            fl!("sun-day-length"),
+            format_day_length(sun.day_length()),
+        ))
+        .push(detail_row(fl!("sun-civil-twilight"), twilight(sun.civil)))
+        .push(detail_row(
+            fl!("sun-nautical-twilight"),
+            twilight(sun.nautical),
+        ))
+        .into()
+}
+
+/// Current moon phase and illuminated fraction
+pub fn view_moon<'a, M: 'a>(moon: &Moon) -> Element<'a, M> {
+    let phase = moon.phase();
+    column()
+        .spacing(8)
+        .push(detail_row(
+            fl!("moon-phase"),
+            format!("{} {}", phase.glyph(), phase.label()),
+        ))
+        .push(detail_row(
+            fl!("moon-illumination"),
+            format!("{:.0}%", moon.illumination() * 100.0),
+        ))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    const BERLIN: Coordinates = Coordinates {
+        latitude: 52.52,
+        longitude: 13.405,
+    };
+
+    const TROMSO: Coordinates = Coordinates {
+        latitude: 69.65,
+        longitude: 18.96,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Minutes after local midnight
+    fn minutes(time: DateTime<FixedOffset>) -> i64 {
+        i64::from(time.hour() * 60 + time.minute())
+    }
+
+    #[test]
+    fn test_berlin_summer_solstice() {
+        let sun = SunTimes::compute(date(2025, 6, 21), BERLIN, &chrono_tz::Europe::Berlin);
+        let Crossing::RiseSet { rise, set } = sun.daylight else {
+            panic!("sun must rise in Berlin: {sun:?}");
+        };
+        // Published times: 04:43 and 21:33 CEST
+        assert!((minutes(rise) - (4 * 60 + 43)).abs() <= 2, "{rise}");
+        assert!((minutes(set) - (21 * 60 + 33)).abs() <= 2, "{set}");
+        assert_eq!(rise.offset().local_minus_utc(), 2 * 3600, "summer time");
+
+        assert!(sun.day_length() > TimeDelta::minutes(16 * 60 + 45));
+        // The sun dips only 14° below the horizon: nautical dusk near midnight
+        let Crossing::RiseSet { set: dusk, .. } = sun.nautical else {
+            panic!("nautical twilight must end: {sun:?}");
+        };
+        assert_eq!(dusk.hour(), 23);
+    }
+
+    #[test]
+    fn test_equator_day_is_about_twelve_hours() {
+        let quito = Coordinates {
+            latitude: 0.0,
+            longitude: -78.5,
+        };
+        let sun = SunTimes::compute(date(2025, 3, 20), quito, &Utc);
+        let length = sun.day_length().num_minutes();
+        assert!((12 * 60..=12 * 60 + 10).contains(&length), "{length}");
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let summer = SunTimes::compute(date(2025, 6, 21), TROMSO, &Utc);
+        assert_eq!(summer.daylight, Crossing::AlwaysAbove);
+        assert_eq!(summer.day_length(), TimeDelta::days(1));
+
+        // The sun stays just below the horizon: civil twilight at midday
+        let winter = SunTimes::compute(date(2025, 12, 21), TROMSO, &Utc);
+        assert_eq!(winter.daylight, Crossing::AlwaysBelow);
+        assert_eq!(winter.day_length(), TimeDelta::zero());
+        assert!(matches!(winter.civil, Crossing::RiseSet { .. }));
+    }
+
+    #[test]
+    fn test_principal_moon_phases() {
+        // January 2025: first quarter 6th 23:56, full 13th 22:27,
+        // last quarter 21st 20:31, new moon 29th 12:36 (UTC)
+        assert_eq!(
+            principal_phase(date(2025, 1, 6), &Utc),
+            Some(MoonPhase::FirstQuarter)
+        );
+        assert_eq!(
+            principal_phase(date(2025, 1, 13), &Utc),
+            Some(MoonPhase::Full)
+        );
+        assert_eq!(
+            principal_phase(date(2025, 1, 21), &Utc),
+            Some(MoonPhase::LastQuarter)
+        );
+        assert_eq!(
+            principal_phase(date(2025, 1, 29), &Utc),
+            Some(MoonPhase::New)
+        );
+        assert_eq!(principal_phase(date(2025, 1, 10), &Utc), None);
+
+        // The full moon is on the 14th in Tokyo (07:27 JST)
+        let tokyo = chrono_tz::Asia::Tokyo;
+        assert_eq!(principal_phase(date(2025, 1, 13), &tokyo), None);
+        assert_eq!(
+            principal_phase(date(2025, 1, 14), &tokyo),
+            Some(MoonPhase::Full)
+        );
+
+        let phases = (1..=31)
+            .filter_map(|day| principal_phase(date(2025, 1, day), &Utc))
+            .count();
+        assert_eq!(phases, 4);
+    }
+
+    #[test]
+    fn test_moon_illumination() {
+        let at = |text: &str| Moon::at(text.parse::<DateTime<Utc>>().unwrap());
+
+        let full = at("2025-01-13T22:27:00Z");
+        assert_eq!(full.phase(), MoonPhase::Full);
+        assert!(full.illumination() > 0.99);
+
+        let new = at("2025-01-29T12:36:00Z");
+        assert_eq!(new.phase(), MoonPhase::New);
+        assert!(new.illumination() < 0.01);
+
+        let quarter = at("2025-01-06T23:56:00Z");
+        assert_eq!(quarter.phase(), MoonPhase::FirstQuarter);
+        assert!((quarter.illumination() - 0.5).abs() < 0.02);
+
+        assert_eq!(at("2025-01-17T12:00:00Z").phase(), MoonPhase::WaningGibbous);
+    }
+}
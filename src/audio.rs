@@ -0,0 +1,317 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Alarm sound playback
+//!
+//! Plays the sound configured for a notification source when a timer fires.
+//! Decoding and output are delegated to the desktop sound server through its
+//! command line players (`pw-play` for PipeWire, `paplay` for PulseAudio), so
+//! no audio stack is linked into the applet. When neither player works (no
+//! sound server, no output device, missing file) the alarm degrades to the
+//! silent desktop notification.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::sync::oneshot;
+
+use crate::NotificationSource;
+use crate::config::TimeAppletConfig;
+
+/// Upper bound for looping alarms, in case the notification is never dismissed
+const MAX_LOOP_DURATION: Duration = Duration::from_secs(120);
+
+/// Silence between two repetitions of a looping alarm
+const LOOP_GAP: Duration = Duration::from_millis(500);
+
+/// Sound of the freedesktop sound theme, played when a sound name is not
+/// found in the sounds directory (e.g. no sounds were installed)
+const THEME_ALARM_SOUND: &str = "/usr/share/sounds/freedesktop/stereo/alarm-clock-elapsed.oga";
+
+// ============================================================================
+// Alarm Sound
+// ============================================================================
+
+/// A sound ready to be played for one notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmSound {
+    path: PathBuf,
+    /// Linear volume in 0.0..=1.0
+    volume: f32,
+    /// Repeat until stopped (or `MAX_LOOP_DURATION` elapses)
+    looping: bool,
+}
+
+impl AlarmSound {
+    /// Resolve the sound configured for `source`, or `None` if it should be silent
+    ///
+    /// Sound settings are either file names looked up with
+    /// `paths::get_audio_path` (falling back to the freedesktop theme's alarm
+    /// sound when missing) or absolute paths to custom files. An empty setting
+    /// disables the sound for that source only.
+    pub fn for_source(source: NotificationSource, config: &TimeAppletConfig) -> Option<Self> {
+        if !config.alarm_sound_enabled {
+            return None;
+        }
+
+        let name = match source {
+            NotificationSource::Timer(_) => &config.countdown_sound,
+            NotificationSource::Pomodoro => &config.pomodoro_sound,
+            NotificationSource::Alarm(_) => &config.alarm_sound,
+            // Alerts are informational: the notification is enough
+            NotificationSource::WeatherAlert => return None,
+        };
+        if name.is_empty() {
+            return None;
+        }
+
+        let custom = PathBuf::from(name);
+        let path = if custom.is_absolute() {
+            custom
+        } else {
+            installed_or_theme_sound(
+                crate::paths::get_audio_path(name),
+                Path::new(THEME_ALARM_SOUND),
+            )
+        };
+
+        Some(Self {
+            path,
+            volume: f32::from(config.alarm_volume.min(100)) / 100.0,
+            // Pomodoro phases advance on their own: a short chime is enough
+            looping: config.alarm_loop && source != NotificationSource::Pomodoro,
+        })
+    }
+
+    /// Whether the alarm keeps ringing until its notification is dismissed
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+}
+
+/// `installed` if the file exists, else the theme sound (if that exists)
+fn installed_or_theme_sound(installed: PathBuf, theme: &Path) -> PathBuf {
+    if installed.exists() || !theme.exists() {
+        return installed;
+    }
+    tracing::debug!(
+        "[Audio] {} not found, using {}",
+        installed.display(),
+        theme.display()
+    );
+    theme.to_path_buf()
+}
+
+// ============================================================================
+// Playback
+// ============================================================================
+
+/// Why an alarm could not be played
+#[derive(Debug)]
+pub enum AudioError {
+    /// Sound file does not exist
+    MissingFile(PathBuf),
+    /// None of the supported players is installed
+    NoPlayer,
+    /// Player ran but failed (typically no sound server or output device)
+    PlayerFailed(String),
+}
+
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioError::MissingFile(path) => write!(f, "sound file not found: {}", path.display()),
+            AudioError::NoPlayer => write!(f, "neither pw-play nor paplay is available"),
+            AudioError::PlayerFailed(status) => write!(f, "player exited with {}", status),
+        }
+    }
+}
+
+/// Handle of a sound being played
+///
+/// Dropping the handle stops playback, so ringing alarms are silenced by
+/// simply removing them from wherever they are stored.
+#[derive(Debug)]
+pub struct Playback {
+    _stop: oneshot::Sender<()>,
+}
+
+/// Start playing `sound`
+///
+/// Returns the handle that stops it, and the future that performs playback;
+/// the future must be driven by the runtime (e.g. through `Task::perform`).
+pub fn play(
+    sound: AlarmSound,
+) -> (
+    Playback,
+    impl Future<Output = Result<(), AudioError>> + Send + 'static,
+) {
+    let (stop_tx, stop_rx) = oneshot::channel();
+    (Playback { _stop: stop_tx }, run(sound, stop_rx))
+}
+
+async fn run(sound: AlarmSound, mut stop: oneshot::Receiver<()>) -> Result<(), AudioError> {
+    if !sound.path.exists() {
+        return Err(AudioError::MissingFile(sound.path));
+    }
+
+    let playback = async {
+        loop {
+            play_once(&sound).await?;
+            if !sound.looping {
+                return Ok(());
+            }
+            tokio::time::sleep(LOOP_GAP).await;
+        }
+    };
+
+    // Either the handle is dropped (Ok or Err from the receiver) or the
+    // loop ends; the running player is killed when its future is dropped
+    tokio::select! {
+        result = playback => result,
+        _ = &mut stop => Ok(()),
+        () = tokio::time::sleep(MAX_LOOP_DURATION) => {
+            tracing::debug!("[Audio] Alarm loop limit reached");
+            Ok(())
+        }
+    }
+}
+
+/// Play the file once with the first available player that succeeds
+async fn play_once(sound: &AlarmSound) -> Result<(), AudioError> {
+    play_first(player_commands(sound)).await
+}
+
+/// Run the players in turn until one succeeds; a player that is installed but
+/// fails (e.g. `pw-play` without PipeWire) falls through to the next one, and
+/// only the last failure is reported
+async fn play_first(commands: impl IntoIterator<Item = Command>) -> Result<(), AudioError> {
+    let mut failure = AudioError::NoPlayer;
+    for mut command in commands {
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            // Not installed: try the next player
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                failure = AudioError::PlayerFailed(err.to_string());
+                continue;
+            }
+        };
+
+        match child.wait().await {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => failure = AudioError::PlayerFailed(status.to_string()),
+            Err(err) => failure = AudioError::PlayerFailed(err.to_string()),
+        }
+        tracing::debug!(
+            "[Audio] {:?} failed: {}",
+            command.as_std().get_program(),
+            failure
+        );
+    }
+    Err(failure)
+}
+
+/// Candidate players, most preferred first
+fn player_commands(sound: &AlarmSound) -> [Command; 2] {
+    let mut pw_play = Command::new("pw-play");
+    pw_play
+        .arg(format!("--volume={:.2}", sound.volume))
+        .arg(&sound.path);
+
+    let mut paplay = Command::new("paplay");
+    paplay
+        .arg(format!("--volume={}", pulse_volume(sound.volume)))
+        .arg(&sound.path);
+
+    [pw_play, paplay]
+}
+
+/// Convert a linear volume to PulseAudio's scale (65536 = 100%)
+fn pulse_volume(volume: f32) -> u32 {
+    (volume.clamp(0.0, 1.0) * 65536.0).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pulse_volume_scale() {
+        assert_eq!(pulse_volume(0.0), 0);
+        assert_eq!(pulse_volume(0.5), 32768);
+        assert_eq!(pulse_volume(1.0), 65536);
+        assert_eq!(pulse_volume(2.0), 65536);
+    }
+
+    #[test]
+    fn test_missing_sound_falls_back_to_theme() {
+        let dir = std::env::temp_dir().join(format!("timeplus-audio-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let installed = dir.join("alarm.ogg");
+        let theme = dir.join("alarm-clock-elapsed.oga");
+        std::fs::write(&theme, b"").unwrap();
+
+        assert_eq!(installed_or_theme_sound(installed.clone(), &theme), theme);
+        std::fs::write(&installed, b"").unwrap();
+        assert_eq!(
+            installed_or_theme_sound(installed.clone(), &theme),
+            installed
+        );
+        // Without the theme sound the error names the configured file
+        let missing = dir.join("missing.ogg");
+        assert_eq!(
+            installed_or_theme_sound(missing.clone(), &dir.join("none.oga")),
+            missing
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failing_player_falls_through() {
+        let players = [Command::new("false"), Command::new("true")];
+        assert!(play_first(players).await.is_ok());
+
+        let players = [
+            Command::new("timeplus-missing-player"),
+            Command::new("false"),
+        ];
+        assert!(matches!(
+            play_first(players).await,
+            Err(AudioError::PlayerFailed(_))
+        ));
+        let players = [Command::new("timeplus-missing-player")];
+        assert!(matches!(
+            play_first(players).await,
+            Err(AudioError::NoPlayer)
+        ));
+    }
+
+    #[test]
+    fn test_sound_settings_per_source() {
+        let config = TimeAppletConfig {
+            pomodoro_sound: String::new(),
+            countdown_sound: "/tmp/custom.ogg".to_string(),
+            alarm_volume: 50,
+            ..Default::default()
+        };
+
+        let countdown = AlarmSound::for_source(NotificationSource::Timer(1), &config).unwrap();
+        assert_eq!(countdown.path, PathBuf::from("/tmp/custom.ogg"));
+        assert_eq!(countdown.volume, 0.5);
+        assert!(countdown.is_looping());
+
+        assert_eq!(
+            AlarmSound::for_source(NotificationSource::Pomodoro, &config),
+            None
+        );
+    }
+}
@@ -0,0 +1,860 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! CalDAV synchronization
+//!
+//! Discovers the calendars of the account at `caldav_url` (RFC 4791:
+//! current-user-principal → calendar-home-set → calendar collections) and
+//! keeps a copy of their events in `$XDG_CACHE_HOME/cosmic-applet-timeplus`,
+//! so the Calendar tab works offline. Unchanged calendars are skipped by
+//! their ctag; changed ones are synced with a sync-collection REPORT
+//! (RFC 6578) when the server supports it, or by comparing etags otherwise.
+//! Only new or modified events are downloaded.
+//!
+//! The password is read from the desktop keyring (see `secrets.rs`), never
+//! from the applet config. Any CalDAV server works, including a local
+//! Radicale instance (`http://localhost:5232/`).
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::{Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::ics::{Event, parse_calendar};
+
+/// How often calendars are synced
+const SYNC_INTERVAL: TimeDelta = TimeDelta::minutes(15);
+
+/// Give up on a request after this long
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Events downloaded per calendar-multiget REPORT
+const MULTIGET_BATCH: usize = 50;
+
+/// File name of the synced calendars inside the cache directory
+const CACHE_FILE: &str = "caldav.json";
+
+const DAV: &str = "DAV:";
+const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+const CALENDARSERVER: &str = "http://calendarserver.org/ns/";
+
+const PRINCIPAL_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:current-user-principal/></d:prop></d:propfind>"#;
+
+const HOME_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><c:calendar-home-set/></d:prop></d:propfind>"#;
+
+const CALENDARS_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">
+<d:prop><d:resourcetype/><d:displayname/><cs:getctag/><d:sync-token/><c:supported-calendar-component-set/></d:prop>
+</d:propfind>"#;
+
+const ETAGS_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+<d:prop><d:getetag/></d:prop>
+<c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VEVENT"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+// ============================================================================
+// Errors and Sync Status
+// ============================================================================
+
+/// Why calendars could not be synced
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalDavError {
+    /// Invalid server URL
+    Url(String),
+    /// Connection failed or timed out (typically offline)
+    Request(String),
+    /// Server answered with an error status (401: wrong or missing password)
+    Status(u16),
+    /// Response body is not a WebDAV multistatus
+    Parse(String),
+}
+
+impl std::fmt::Display for CalDavError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalDavError::Url(err) => write!(f, "invalid server URL: {}", err),
+            CalDavError::Request(err) => write!(f, "request failed: {}", err),
+            CalDavError::Status(status) => write!(f, "server returned HTTP {}", status),
+            CalDavError::Parse(err) => write!(f, "unexpected response: {}", err),
+        }
+    }
+}
+
+/// When calendars were last synced, and how it went
+#[derive(Debug, Clone, Default)]
+pub struct SyncStatus {
+    /// A sync is in flight
+    syncing: bool,
+    /// When the last sync was started
+    last_attempt: Option<DateTime<Utc>>,
+    /// Error of the last sync, cleared by the next success
+    pub error: Option<CalDavError>,
+}
+
+impl SyncStatus {
+    /// Check if a sync should be started at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !self.syncing
+            && self
+                .last_attempt
+                .is_none_or(|attempt| now - attempt >= SYNC_INTERVAL)
+    }
+
+    /// Record that a sync was started at `now`
+    pub fn begin(&mut self, now: DateTime<Utc>) {
+        self.syncing = true;
+        self.last_attempt = Some(now);
+    }
+
+    /// Record the outcome of the sync in flight
+    pub fn finish(&mut self, error: Option<CalDavError>) {
+        self.syncing = false;
+        self.error = error;
+    }
+}
+
+// ============================================================================
+// Local Copy
+// ============================================================================
+
+/// Synced calendars of one account
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalDavStore {
+    /// Server URL the calendars were discovered from
+    url: String,
+    calendars: Vec<CachedCalendar>,
+}
+
+/// A calendar collection and its events
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CachedCalendar {
+    href: String,
+    name: String,
+    /// Collection tag of the synced state (changes with any event)
+    ctag: Option<String>,
+    /// Token of the synced state, for the next sync-collection REPORT
+    sync_token: Option<String>,
+    /// iCalendar objects by href
+    objects: BTreeMap<String, CachedObject>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedObject {
+    etag: String,
+    /// iCalendar text
+    data: String,
+}
+
+impl CalDavStore {
+    /// Events of all calendars
+    pub fn events(&self) -> Vec<Event> {
+        self.calendars
+            .iter()
+            .flat_map(|calendar| calendar.objects.values())
+            .flat_map(|object| parse_calendar(&object.data))
+            .collect()
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    crate::paths::get_cache_dir().map(|dir| dir.join(CACHE_FILE))
+}
+
+/// Synced calendars of the account at `url` (empty for another account)
+pub fn load_cache(url: &str) -> CalDavStore {
+    let Some(data) = cache_path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return CalDavStore::default();
+    };
+    match serde_json::from_str::<CalDavStore>(&data) {
+        Ok(store) if store.url == url => store,
+        Ok(_) => CalDavStore::default(),
+        Err(err) => {
+            tracing::warn!("[CalDAV] Ignoring corrupt cache: {}", err);
+            CalDavStore::default()
+        }
+    }
+}
+
+/// Save the synced calendars, logging (not propagating) failures
+fn save_cache(store: &CalDavStore) {
+    let Some(path) = cache_path() else {
+        tracing::warn!("[CalDAV] No cache directory available, calendars not cached");
+        return;
+    };
+    crate::paths::write_json(&path, store, "[CalDAV] Cannot write");
+}
+
+/// Sync the account and return the events of all its calendars
+///
+/// The password is looked up in the keyring; without one (or without a
+/// username) requests are sent unauthenticated. The local copy is only
+/// replaced when the sync succeeds.
+pub async fn refresh(url: String, username: String) -> Result<Vec<Event>, CalDavError> {
+    let password = if username.is_empty() {
+        None
+    } else {
+        crate::secrets::lookup_password(&url, &username).await
+    };
+    let account = Account {
+        url,
+        username,
+        password,
+    };
+    let store = load_cache(&account.url);
+    let store = sync(&account, store).await?;
+    save_cache(&store);
+    Ok(store.events())
+}
+
+// ============================================================================
+// WebDAV Responses
+// ============================================================================
+
+/// One `<response>` of a multistatus, with the properties we ask for
+#[derive(Debug, Default, PartialEq)]
+struct Resource {
+    href: String,
+    /// Gone (404 response status, as in sync-collection reports)
+    deleted: bool,
+    etag: Option<String>,
+    ctag: Option<String>,
+    sync_token: Option<String>,
+    display_name: Option<String>,
+    /// Resource type includes `calendar`
+    is_calendar: bool,
+    /// Calendar accepts VEVENTs (true when the server doesn't say)
+    has_events: bool,
+    calendar_data: Option<String>,
+    /// `current-user-principal` href
+    principal: Option<String>,
+    /// `calendar-home-set` href
+    calendar_home: Option<String>,
+}
+
+/// A multistatus body: its responses and the sync token of the collection
+#[derive(Debug, Default)]
+struct Multistatus {
+    resources: Vec<Resource>,
+    sync_token: Option<String>,
+}
+
+fn is(node: roxmltree::Node, namespace: &str, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && node.tag_name().namespace() == Some(namespace)
+}
+
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    namespace: &str,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|child| is(*child, namespace, name))
+}
+
+fn text_of(node: roxmltree::Node) -> String {
+    node.text().unwrap_or_default().trim().to_string()
+}
+
+/// Href nested in a property (`<current-user-principal><href>…`)
+fn href_in(node: roxmltree::Node) -> Option<String> {
+    child(node, DAV, "href").map(text_of)
+}
+
+/// Check if a `<status>` line is a success ("HTTP/1.1 200 OK")
+fn status_ok(status: roxmltree::Node) -> bool {
+    text_of(status)
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|code| code.starts_with('2'))
+}
+
+fn parse_multistatus(xml: &str) -> Result<Multistatus, CalDavError> {
+    let document =
+        roxmltree::Document::parse(xml).map_err(|err| CalDavError::Parse(err.to_string()))?;
+    let root = document.root_element();
+    if !is(root, DAV, "multistatus") {
+        return Err(CalDavError::Parse(format!(
+            "expected multistatus, got {}",
+            root.tag_name().name()
+        )));
+    }
+
+    let mut multistatus = Multistatus {
+        sync_token: child(root, DAV, "sync-token").map(text_of),
+        ..Multistatus::default()
+    };
+    for response in root.children().filter(|node| is(*node, DAV, "response")) {
+        let mut resource = Resource {
+            href: child(response, DAV, "href")
+                .map(text_of)
+                .unwrap_or_default(),
+            deleted: child(response, DAV, "status").is_some_and(|status| !status_ok(status)),
+            has_events: true,
+            ..Resource::default()
+        };
+        // Properties listed under a non-2xx propstat are missing
+        let props = response
+            .children()
+            .filter(|node| is(*node, DAV, "propstat"))
+            .filter(|propstat| child(*propstat, DAV, "status").is_none_or(status_ok))
+            .filter_map(|propstat| child(propstat, DAV, "prop"))
+            .flat_map(|prop| prop.children().filter(|node| node.is_element()));
+        for prop in props {
+            let namespace = prop.tag_name().namespace().unwrap_or_default();
+            match (namespace, prop.tag_name().name()) {
+                (DAV, "getetag") => resource.etag = Some(text_of(prop)),
+                (DAV, "sync-token") => resource.sync_token = Some(text_of(prop)),
+                (DAV, "displayname") => resource.display_name = Some(text_of(prop)),
+                (DAV, "resourcetype") => {
+                    resource.is_calendar = child(prop, CALDAV, "calendar").is_some()
+                }
+                (DAV, "current-user-principal") => resource.principal = href_in(prop),
+                (CALDAV, "calendar-home-set") => resource.calendar_home = href_in(prop),
+                (CALDAV, "calendar-data") => {
+                    resource.calendar_data = prop.text().map(str::to_string)
+                }
+                (CALDAV, "supported-calendar-component-set") => {
+                    resource.has_events = prop
+                        .children()
+                        .filter(|comp| is(*comp, CALDAV, "comp"))
+                        .any(|comp| comp.attribute("name") == Some("VEVENT"));
+                }
+                (CALENDARSERVER, "getctag") => resource.ctag = Some(text_of(prop)),
+                _ => {}
+            }
+        }
+        multistatus.resources.push(resource);
+    }
+    Ok(multistatus)
+}
+
+/// Escape text for an XML element
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// Where and as whom to sync
+struct Account {
+    url: String,
+    username: String,
+    password: Option<String>,
+}
+
+struct Client {
+    http: reqwest::Client,
+    base: Url,
+    username: String,
+    password: Option<String>,
+}
+
+impl Client {
+    fn new(account: &Account) -> Result<Self, CalDavError> {
+        let base =
+            Url::parse(account.url.trim()).map_err(|err| CalDavError::Url(err.to_string()))?;
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!(
+                "cosmic-applet-timeplus/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .map_err(|err| CalDavError::Request(err.to_string()))?;
+        Ok(Self {
+            http,
+            base,
+            username: account.username.clone(),
+            password: account.password.clone(),
+        })
+    }
+
+    /// Absolute URL of an href returned by the server
+    fn resolve(&self, href: &str) -> Result<Url, CalDavError> {
+        self.base
+            .join(href)
+            .map_err(|err| CalDavError::Url(err.to_string()))
+    }
+
+    /// Send a WebDAV request and parse its multistatus answer
+    async fn send(
+        &self,
+        method: &str,
+        url: &Url,
+        depth: &str,
+        body: String,
+    ) -> Result<Multistatus, CalDavError> {
+        let method = Method::from_bytes(method.as_bytes())
+            .map_err(|err| CalDavError::Request(err.to_string()))?;
+        let mut request = self
+            .http
+            .request(method, url.clone())
+            .header("Depth", depth)
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body);
+        if !self.username.is_empty() {
+            request = request.basic_auth(&self.username, self.password.as_ref());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|err| CalDavError::Request(err.to_string()))?;
+        let status = response.status();
+        if status != StatusCode::MULTI_STATUS && !status.is_success() {
+            return Err(CalDavError::Status(status.as_u16()));
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|err| CalDavError::Request(err.to_string()))?;
+        parse_multistatus(&body)
+    }
+
+    /// Calendar home of the account (falls back to the configured URL for
+    /// servers without principals)
+    async fn discover_home(&self) -> Result<Url, CalDavError> {
+        let principal = self
+            .send("PROPFIND", &self.base, "0", PRINCIPAL_REQUEST.to_string())
+            .await?
+            .resources
+            .into_iter()
+            .find_map(|resource| resource.principal);
+        let principal = match principal {
+            Some(href) => self.resolve(&href)?,
+            None => self.base.clone(),
+        };
+
+        let home = self
+            .send("PROPFIND", &principal, "0", HOME_REQUEST.to_string())
+            .await?
+            .resources
+            .into_iter()
+            .find_map(|resource| resource.calendar_home);
+        match home {
+            Some(href) => self.resolve(&href),
+            None => Ok(principal),
+        }
+    }
+
+    /// Event calendars in the calendar home
+    async fn calendars(&self, home: &Url) -> Result<Vec<Resource>, CalDavError> {
+        let multistatus = self
+            .send("PROPFIND", home, "1", CALENDARS_REQUEST.to_string())
+            .await?;
+        Ok(multistatus
+            .resources
+            .into_iter()
+            .filter(|resource| resource.is_calendar && resource.has_events)
+            .collect())
+    }
+
+    /// Bring a cached calendar up to date with the server's `remote` state
+    async fn sync_calendar(
+        &self,
+        mut calendar: CachedCalendar,
+        remote: &Resource,
+    ) -> Result<CachedCalendar, CalDavError> {
+        calendar.name = remote.display_name.clone().unwrap_or_default();
+        if remote.ctag.is_some() && remote.ctag == calendar.ctag {
+            tracing::debug!("[CalDAV] {} unchanged", calendar.href);
+            return Ok(calendar);
+        }
+
+        let url = self.resolve(&calendar.href)?;
+        let incremental = match (calendar.sync_token.clone(), &remote.sync_token) {
+            (Some(token), Some(_)) => match self.sync_collection(&url, &token, &mut calendar).await
+            {
+                Ok(changed) => Some(changed),
+                Err(err) => {
+                    // Expired token (or no RFC 6578 support after all)
+                    tracing::debug!("[CalDAV] sync-collection failed ({}), comparing etags", err);
+                    None
+                }
+            },
+            _ => None,
+        };
+        let changed = match incremental {
+            Some(changed) => changed,
+            None => {
+                let changed = self.compare_etags(&url, &mut calendar).await?;
+                calendar.sync_token = remote.sync_token.clone();
+                changed
+            }
+        };
+
+        tracing::debug!(
+            "[CalDAV] {}: {} changed events",
+            calendar.href,
+            changed.len()
+        );
+        for hrefs in changed.chunks(MULTIGET_BATCH) {
+            self.download(&url, hrefs, &mut calendar).await?;
+        }
+        calendar.ctag = remote.ctag.clone();
+        Ok(calendar)
+    }
+
+    /// Apply a sync-collection REPORT since `token`; returns the changed hrefs
+    async fn sync_collection(
+        &self,
+        url: &Url,
+        token: &str,
+        calendar: &mut CachedCalendar,
+    ) -> Result<Vec<String>, CalDavError> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<d:sync-collection xmlns:d="DAV:"><d:sync-token>{}</d:sync-token><d:sync-level>1</d:sync-level><d:prop><d:getetag/></d:prop></d:sync-collection>"#,
+            escape_xml(token)
+        );
+        let multistatus = self.send("REPORT", url, "1", body).await?;
+
+        let mut changed = Vec::new();
+        for resource in multistatus.resources {
+            if resource.deleted {
+                calendar.objects.remove(&resource.href);
+            } else if let Some(etag) = resource.etag {
+                let cached = calendar.objects.get(&resource.href);
+                if cached.is_none_or(|object| object.etag != etag) {
+                    changed.push(resource.href);
+                }
+            }
+        }
+        calendar.sync_token = multistatus.sync_token;
+        Ok(changed)
+    }
+
+    /// List the etags of all events, dropping deleted ones from the cache;
+    /// returns the hrefs that are new or modified
+    async fn compare_etags(
+        &self,
+        url: &Url,
+        calendar: &mut CachedCalendar,
+    ) -> Result<Vec<String>, CalDavError> {
+        let multistatus = self
+            .send("REPORT", url, "1", ETAGS_REQUEST.to_string())
+            .await?;
+        let etags: BTreeMap<String, String> = multistatus
+            .resources
+            .into_iter()
+            .filter_map(|resource| Some((resource.href, resource.etag?)))
+            .collect();
+
+        calendar.objects.retain(|href, _| etags.contains_key(href));
+        Ok(etags
+            .into_iter()
+            .filter(|(href, etag)| {
+                calendar
+                    .objects
+                    .get(href)
+                    .is_none_or(|object| object.etag != *etag)
+            })
+            .map(|(href, _)| href)
+            .collect())
+    }
+
+    /// Download events with a calendar-multiget REPORT
+    async fn download(
+        &self,
+        url: &Url,
+        hrefs: &[String],
+        calendar: &mut CachedCalendar,
+    ) -> Result<(), CalDavError> {
+        let hrefs: String = hrefs
+            .iter()
+            .map(|href| format!("<d:href>{}</d:href>", escape_xml(href)))
+            .collect();
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><d:getetag/><c:calendar-data/></d:prop>{hrefs}</c:calendar-multiget>"#
+        );
+        let multistatus = self.send("REPORT", url, "1", body).await?;
+        for resource in multistatus.resources {
+            if let Some(data) = resource.calendar_data {
+                let etag = resource.etag.unwrap_or_default();
+                calendar
+                    .objects
+                    .insert(resource.href, CachedObject { etag, data });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sync all event calendars of `account` into `store`
+///
+/// A calendar that fails to sync keeps its previous copy; calendars deleted
+/// on the server are dropped.
+async fn sync(account: &Account, mut store: CalDavStore) -> Result<CalDavStore, CalDavError> {
+    let client = Client::new(account)?;
+    if store.url != account.url {
+        store = CalDavStore {
+            url: account.url.clone(),
+            calendars: Vec::new(),
+        };
+    }
+
+    let home = client.discover_home().await?;
+    let remote = client.calendars(&home).await?;
+    tracing::debug!("[CalDAV] {} calendars in {}", remote.len(), home);
+
+    let mut calendars = Vec::with_capacity(remote.len());
+    for resource in &remote {
+        let cached = store
+            .calendars
+            .iter()
+            .find(|calendar| calendar.href == resource.href)
+            .cloned()
+            .unwrap_or_else(|| CachedCalendar {
+                href: resource.href.clone(),
+                ..CachedCalendar::default()
+            });
+        let calendar = match client.sync_calendar(cached.clone(), resource).await {
+            Ok(calendar) => calendar,
+            Err(err) => {
+                tracing::warn!("[CalDAV] Cannot sync {}: {}", resource.href, err);
+                cached
+            }
+        };
+        calendars.push(calendar);
+    }
+    store.calendars = calendars;
+    Ok(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const CALENDARS_RESPONSE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<multistatus xmlns="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav" xmlns:CS="http://calendarserver.org/ns/">
+  <response>
+    <href>/alice/</href>
+    <propstat><prop><resourcetype><collection/></resourcetype></prop><status>HTTP/1.1 200 OK</status></propstat>
+    <propstat><prop><CS:getctag/></prop><status>HTTP/1.1 404 Not Found</status></propstat>
+  </response>
+  <response>
+    <href>/alice/work/</href>
+    <propstat>
+      <prop>
+        <resourcetype><collection/><C:calendar/></resourcetype>
+        <displayname>Work</displayname>
+        <CS:getctag>"ctag-1"</CS:getctag>
+        <sync-token>http://radicale.org/ns/sync/1</sync-token>
+        <C:supported-calendar-component-set><C:comp name="VEVENT"/></C:supported-calendar-component-set>
+      </prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+  <response>
+    <href>/alice/tasks/</href>
+    <propstat>
+      <prop>
+        <resourcetype><collection/><C:calendar/></resourcetype>
+        <C:supported-calendar-component-set><C:comp name="VTODO"/></C:supported-calendar-component-set>
+      </prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+</multistatus>"#;
+
+    fn event_data(summary: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:{summary}\r\nSUMMARY:{summary}\r\nDTSTART;VALUE=DATE:20250110\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+        )
+    }
+
+    fn multiget_response(items: &[(&str, &str, &str)]) -> String {
+        let responses: String = items
+            .iter()
+            .map(|(href, etag, summary)| {
+                format!(
+                    "<d:response><d:href>{href}</d:href><d:propstat><d:prop><d:getetag>{etag}</d:getetag><c:calendar-data>{}</c:calendar-data></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+                    event_data(summary)
+                )
+            })
+            .collect();
+        format!(
+            r#"<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">{responses}</d:multistatus>"#
+        )
+    }
+
+    fn etags_response(items: &[(&str, &str)]) -> String {
+        let responses: String = items
+            .iter()
+            .map(|(href, etag)| {
+                format!(
+                    "<d:response><d:href>{href}</d:href><d:propstat><d:prop><d:getetag>{etag}</d:getetag></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>"
+                )
+            })
+            .collect();
+        format!(r#"<d:multistatus xmlns:d="DAV:">{responses}</d:multistatus>"#)
+    }
+
+    /// Answer each request with the next canned response (a local CalDAV
+    /// stand-in); returns the base URL and the "METHOD path" lines received
+    async fn serve(responses: Vec<String>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 8192];
+                // Read the headers and the body announced by content-length
+                loop {
+                    let read = socket.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text[..end]
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|value| value.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length || read == 0 {
+                            break;
+                        }
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let line = request.lines().next().unwrap_or_default();
+                requests.push(
+                    line.rsplit_once(' ')
+                        .map_or(line, |(start, _)| start)
+                        .to_string(),
+                );
+
+                let response = format!(
+                    "HTTP/1.1 207 Multi-Status\r\ncontent-type: application/xml\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_parse_calendar_collections() {
+        let multistatus = parse_multistatus(CALENDARS_RESPONSE).unwrap();
+        assert_eq!(multistatus.resources.len(), 3);
+
+        let home = &multistatus.resources[0];
+        assert!(!home.is_calendar);
+        assert_eq!(home.ctag, None, "404 propstat");
+
+        let work = &multistatus.resources[1];
+        assert!(work.is_calendar && work.has_events);
+        assert_eq!(work.display_name.as_deref(), Some("Work"));
+        assert_eq!(work.ctag.as_deref(), Some("\"ctag-1\""));
+        assert_eq!(
+            work.sync_token.as_deref(),
+            Some("http://radicale.org/ns/sync/1")
+        );
+
+        let tasks = &multistatus.resources[2];
+        assert!(tasks.is_calendar && !tasks.has_events);
+
+        assert!(parse_multistatus("<html/>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_against_local_server() {
+        let principal = r#"<d:multistatus xmlns:d="DAV:"><d:response><d:href>/</d:href><d:propstat><d:prop><d:current-user-principal><d:href>/alice/</d:href></d:current-user-principal></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response></d:multistatus>"#;
+        let home = r#"<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:response><d:href>/alice/</d:href><d:propstat><d:prop><c:calendar-home-set><d:href>/alice/</d:href></c:calendar-home-set></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response></d:multistatus>"#;
+
+        // First sync: full etag listing, then download of both events
+        let (url, server) = serve(vec![
+            principal.to_string(),
+            home.to_string(),
+            CALENDARS_RESPONSE.to_string(),
+            etags_response(&[
+                ("/alice/work/a.ics", "\"1\""),
+                ("/alice/work/b.ics", "\"1\""),
+            ]),
+            multiget_response(&[
+                ("/alice/work/a.ics", "\"1\"", "Planning"),
+                ("/alice/work/b.ics", "\"1\"", "Review"),
+            ]),
+        ])
+        .await;
+        let account = Account {
+            url: url.clone(),
+            username: String::new(),
+            password: None,
+        };
+        let store = sync(&account, CalDavStore::default()).await.unwrap();
+        let requests = server.await.unwrap();
+        assert_eq!(
+            requests,
+            vec![
+                "PROPFIND /",
+                "PROPFIND /alice/",
+                "PROPFIND /alice/",
+                "REPORT /alice/work/",
+                "REPORT /alice/work/",
+            ]
+        );
+        let mut summaries: Vec<String> = store
+            .events()
+            .into_iter()
+            .map(|event| event.summary)
+            .collect();
+        summaries.sort();
+        assert_eq!(summaries, vec!["Planning", "Review"]);
+        assert_eq!(
+            store.calendars[0].sync_token.as_deref(),
+            Some("http://radicale.org/ns/sync/1")
+        );
+
+        // Second sync: ctag changed, sync-collection reports one edit and one
+        // deletion; only the edited event is downloaded
+        let changed_calendars = CALENDARS_RESPONSE.replace("ctag-1", "ctag-2");
+        let sync_report = r#"<d:multistatus xmlns:d="DAV:">
+<d:response><d:href>/alice/work/a.ics</d:href><d:propstat><d:prop><d:getetag>"2"</d:getetag></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>
+<d:response><d:href>/alice/work/b.ics</d:href><d:status>HTTP/1.1 404 Not Found</d:status></d:response>
+<d:sync-token>http://radicale.org/ns/sync/2</d:sync-token>
+</d:multistatus>"#;
+        let (url2, server) = serve(vec![
+            principal.to_string(),
+            home.to_string(),
+            changed_calendars,
+            sync_report.to_string(),
+            multiget_response(&[("/alice/work/a.ics", "\"2\"", "Planning (moved)")]),
+        ])
+        .await;
+        // Same account on a new port: keep the cache
+        let mut store = store;
+        store.url = url2.clone();
+        let account = Account {
+            url: url2,
+            ..account
+        };
+        let store = sync(&account, store).await.unwrap();
+        assert_eq!(server.await.unwrap().len(), 5);
+        let events = store.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Planning (moved)");
+        assert_eq!(store.calendars[0].ctag.as_deref(), Some("\"ctag-2\""));
+        assert_eq!(
+            store.calendars[0].sync_token.as_deref(),
+            Some("http://radicale.org/ns/sync/2")
+        );
+    }
+}
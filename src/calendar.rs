@@ -1,29 +1,76 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use chrono::{Datelike, Days, NaiveDate, Timelike, Weekday};
+use chrono::{Datelike, Days, FixedOffset, NaiveDate, Timelike, Weekday};
 use cosmic::{
     Apply, Element,
     iced::{
         Alignment, Length,
-        widget::{column, row},
+        widget::{Row, column, row},
     },
     widget::{self, Button, Grid, button, container, grid, text},
 };
 use icu::{
+    calendar::Gregorian,
     datetime::{
-        DateTimeFormatter, DateTimeFormatterPreferences, fieldsets,
+        DateTimeFormatter, DateTimeFormatterPreferences, FixedCalendarDateTimeFormatter, fieldsets,
         input::{Date, DateTime, Time},
+        options::TimePrecision,
+    },
+    locale::{
+        Locale,
+        extensions::unicode::{Key, Value, key, value},
     },
-    locale::Locale,
 };
 
-use crate::icons;
+use crate::astronomy::{MoonPhase, SunTimes, principal_phase, view_sun_times};
+use crate::caldav::{CalDavError, SyncStatus};
+use crate::events::{Agenda, EventSource, MAX_AGENDA_DAYS};
+use crate::holidays::Holiday;
+use crate::ics::{Event, Occurrence, OccurrenceTime};
+use crate::{fl, icons};
 
 // Calendar layout constants
 const CALENDAR_DAYS: usize = 42; // 6 weeks × 7 days
 const DAY_BUTTON_SIZE: f32 = 44.0; // COSMIC HIG standard button size
 const HEADER_PADDING: [u16; 2] = [12, 20]; // Vertical, Horizontal padding
+const MARKER_SIZE: f32 = 8.0; // Moon phase / event markers, fit under the day number
+const WEEK_NUMBER_WIDTH: f32 = 28.0; // Leading week number column
+const SECONDARY_DAY_SIZE: f32 = 9.0; // Small-print day of the secondary calendar
+const MINI_DAY_SIZE: f32 = 10.0; // Day numbers of the year overview
+const MINI_DAY_WIDTH: f32 = 14.0; // Fits two digits at MINI_DAY_SIZE
+const YEAR_COLUMNS: u32 = 3; // Mini months per row of the year overview
+const AGENDA_HEIGHT: f32 = 360.0; // The agenda list scrolls past this
+const EVENT_DOT: &str = "●";
+
+/// Unicode extension keyword selecting the calendar system (`-u-ca-`)
+const CALENDAR_KEY: Key = key!("ca");
+
+/// CLDR calendar identifiers ICU can format
+const CALENDARS: [&str; 18] = [
+    "buddhist",
+    "chinese",
+    "coptic",
+    "dangi",
+    "ethioaa",
+    "ethiopic",
+    "gregory",
+    "hebrew",
+    "indian",
+    "islamic",
+    "islamic-civil",
+    "islamic-rgsa",
+    "islamic-tbla",
+    "islamic-umalqura",
+    "iso8601",
+    "japanese",
+    "persian",
+    "roc",
+];
+
+/// Calendars with the Gregorian days and months (only years are numbered
+/// differently): shown in the header only
+const GREGORIAN_DAYS: [&str; 3] = ["buddhist", "japanese", "roc"];
 
 // ============================================================================
 // Calendar State Management
@@ -34,6 +81,32 @@ const HEADER_PADDING: [u16; 2] = [12, 20]; // Vertical, Horizontal padding
 pub struct CalendarState {
     pub date_today: NaiveDate,
     pub date_selected: NaiveDate,
+    /// Events of the ICS files and CalDAV, expanded for the visible dates by
+    /// Window
+    pub agenda: Agenda,
+    /// CalDAV sync schedule, started by Window
+    pub caldav: SyncStatus,
+    /// Public holidays on the visible dates, computed by Window
+    pub holidays: Vec<Holiday>,
+    /// Month grid, or year overview of the selected date's year
+    pub view: CalendarView,
+    /// Text of the "jump to date" entry
+    pub jump_query: String,
+    /// The last jump could not be parsed
+    pub jump_invalid: bool,
+    /// Agenda entry whose details are shown
+    pub event_details: Option<Occurrence>,
+}
+
+/// What the Calendar tab shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CalendarView {
+    #[default]
+    Month,
+    /// Twelve mini months, to pick one
+    Year,
+    /// Events of the coming days, by day
+    Agenda,
 }
 
 impl CalendarState {
@@ -43,13 +116,37 @@ impl CalendarState {
         Self {
             date_today: today,
             date_selected: today,
+            agenda: Agenda::default(),
+            caldav: SyncStatus::default(),
+            holidays: Vec::new(),
+            view: CalendarView::Month,
+            jump_query: String::new(),
+            jump_invalid: false,
+            event_details: None,
         }
     }
 
+    /// Public holidays on `date`
+    fn holidays_on(&self, date: NaiveDate) -> impl Iterator<Item = &Holiday> {
+        self.holidays
+            .iter()
+            .filter(move |holiday| holiday.date == date)
+    }
+
     /// Reset calendar to today's date
     pub fn reset_to_today(&mut self, now: chrono::DateTime<chrono::FixedOffset>) {
         self.date_today = NaiveDate::from(now.naive_local());
         self.date_selected = self.date_today;
+        self.view = CalendarView::Month;
+        self.jump_query.clear();
+        self.jump_invalid = false;
+        self.event_details = None;
+    }
+
+    /// Select `date` in the month grid
+    fn go_to(&mut self, date: NaiveDate) {
+        self.date_selected = date;
+        self.view = CalendarView::Month;
     }
 
     /// Update calendar state based on message
@@ -82,10 +179,144 @@ impl CalendarState {
                     tracing::error!("invalid naivedate");
                 }
             }
+            CalendarMessage::ShowYear => self.view = CalendarView::Year,
+            CalendarMessage::ShowMonth(month) => {
+                match clamp_day(self.date_selected.year(), month, self.date_selected.day()) {
+                    Some(date) => self.go_to(date),
+                    None => tracing::error!("invalid naivedate"),
+                }
+            }
+            CalendarMessage::PreviousYear => {
+                if let Some(date) = self
+                    .date_selected
+                    .checked_sub_months(chrono::Months::new(12))
+                {
+                    self.date_selected = date;
+                } else {
+                    tracing::error!("invalid naivedate");
+                }
+            }
+            CalendarMessage::NextYear => {
+                if let Some(date) = self
+                    .date_selected
+                    .checked_add_months(chrono::Months::new(12))
+                {
+                    self.date_selected = date;
+                } else {
+                    tracing::error!("invalid naivedate");
+                }
+            }
+            CalendarMessage::JumpInput(query) => {
+                self.jump_query = query;
+                self.jump_invalid = false;
+            }
+            CalendarMessage::JumpToDate => match parse_jump(&self.jump_query, self.date_today) {
+                Some(date) => {
+                    self.go_to(date);
+                    self.jump_query.clear();
+                }
+                None => self.jump_invalid = true,
+            },
+            CalendarMessage::ShowAgenda => {
+                self.view = CalendarView::Agenda;
+                self.event_details = None;
+            }
+            CalendarMessage::CloseAgenda => self.view = CalendarView::Month,
+            CalendarMessage::ToggleEventDetails(index) => {
+                let occurrence = self.agenda.upcoming().get(index).cloned();
+                self.event_details = if self.event_details == occurrence {
+                    None
+                } else {
+                    occurrence
+                };
+            }
+            CalendarMessage::EventsLoaded(events) => {
+                self.agenda.set_events(EventSource::Files, events);
+            }
+            CalendarMessage::CalDavSynced(_, result) => match result {
+                Ok(events) => {
+                    self.caldav.finish(None);
+                    self.agenda.set_events(EventSource::CalDav, events);
+                }
+                // Keep showing the previously synced events
+                Err(err) => self.caldav.finish(Some(err)),
+            },
         }
     }
 }
 
+// ============================================================================
+// Display Options
+// ============================================================================
+
+/// A calendar system shown alongside the Gregorian grid (Hebrew, Hijri,
+/// Persian, Chinese...)
+#[derive(Debug, Clone)]
+pub struct SecondaryCalendar {
+    /// The display locale with the calendar's `-u-ca-` keyword
+    locale: Locale,
+    /// Days differ from the Gregorian ones: show them under each day
+    show_days: bool,
+}
+
+impl SecondaryCalendar {
+    /// The configured calendar (a CLDR identifier such as `hebrew` or
+    /// `islamic-umalqura`), else the one of the locale's `-u-ca-` extension
+    ///
+    /// `None` for the Gregorian calendar and unknown identifiers.
+    pub fn new(configured: &str, locale: &Locale) -> Option<Self> {
+        let id = match configured.trim() {
+            "" => locale
+                .extensions
+                .unicode
+                .keywords
+                .get(&CALENDAR_KEY)?
+                .to_string(),
+            id => id.to_ascii_lowercase(),
+        };
+        if matches!(id.as_str(), "gregory" | "iso8601") || !CALENDARS.contains(&id.as_str()) {
+            return None;
+        }
+        let mut locale = locale.clone();
+        locale
+            .extensions
+            .unicode
+            .keywords
+            .set(CALENDAR_KEY, Value::try_from_str(&id).ok()?);
+        Some(Self {
+            locale,
+            show_days: !GREGORIAN_DAYS.contains(&id.as_str()),
+        })
+    }
+}
+
+/// How the calendar grid and agenda are drawn, from the config
+#[derive(Debug, Clone)]
+pub struct GridOptions {
+    /// First day of the week (see `TimeAppletConfig::first_day_of_week`)
+    pub first_day_of_week: u8,
+    /// UTC offset of the days moon phases are marked on (`None` hides them)
+    pub moon_offset: Option<FixedOffset>,
+    /// Leading week number column
+    pub week_numbers: bool,
+    /// Dates in another calendar system, in small print
+    pub secondary_calendar: Option<SecondaryCalendar>,
+    /// Days the agenda lists, today included
+    pub agenda_days: u32,
+}
+
+/// Formatter preferences of the Gregorian calendar the grid is laid out in,
+/// whatever the locale's `-u-ca-` extension says
+fn gregorian_prefs(locale: &Locale) -> DateTimeFormatterPreferences {
+    let mut locale = locale.clone();
+    locale
+        .extensions
+        .unicode
+        .keywords
+        .set(CALENDAR_KEY, value!("gregory"));
+    DateTimeFormatterPreferences::from(locale)
+}
+
 // ============================================================================
 // Calendar Messages
 // ============================================================================
@@ -96,6 +327,26 @@ pub enum CalendarMessage {
     SelectDay(u32),
     PreviousMonth,
     NextMonth,
+    /// Open the year overview (header date clicked)
+    ShowYear,
+    /// Open a month of the overview's year
+    ShowMonth(u32),
+    PreviousYear,
+    NextYear,
+    JumpInput(String),
+    /// Go to the date typed in the entry
+    JumpToDate,
+    /// List the upcoming events instead of the month grid
+    ShowAgenda,
+    /// Back to the month grid
+    CloseAgenda,
+    /// Show or hide the details of an upcoming occurrence (index in
+    /// `Agenda::upcoming`)
+    ToggleEventDetails(usize),
+    /// Events read from the configured ICS files
+    EventsLoaded(Vec<Event>),
+    /// Events of all CalDAV calendars after a sync of the server at the URL
+    CalDavSynced(String, Result<Vec<Event>, CalDavError>),
 }
 
 // ============================================================================
@@ -109,6 +360,73 @@ fn get_calendar_first(year: i32, month: u32, from_weekday: Weekday) -> NaiveDate
     date.checked_sub_days(Days::new(num_days as u64)).unwrap()
 }
 
+/// `day` of the month, or the month's last day if it is shorter
+fn clamp_day(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    (1..=day)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+/// Date typed in the "jump to date" entry: ISO `2026-10-18`, a month
+/// `2026-10` (its first day), or an offset from `today` in days, weeks,
+/// months or years (`+10d`, `-2w`, `+6m`, `+1y`)
+fn parse_jump(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim();
+    if let Some(offset) = text.strip_prefix(['+', '-']) {
+        let forward = text.starts_with('+');
+        let unit = offset.chars().last()?;
+        let amount: u32 = offset[..offset.len() - unit.len_utf8()].parse().ok()?;
+        let (days, months) = match unit.to_ascii_lowercase() {
+            'd' => (amount, 0),
+            'w' => (amount.checked_mul(7)?, 0),
+            'm' => (0, amount),
+            'y' => (0, amount.checked_mul(12)?),
+            _ => return None,
+        };
+        return if forward {
+            today
+                .checked_add_months(chrono::Months::new(months))?
+                .checked_add_days(Days::new(days.into()))
+        } else {
+            today
+                .checked_sub_months(chrono::Months::new(months))?
+                .checked_sub_days(Days::new(days.into()))
+        };
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{text}-01"), "%Y-%m-%d"))
+        .ok()
+}
+
+/// First and last dates shown by the grid for the month of `date_selected`
+pub fn visible_range(date_selected: NaiveDate, first_day_of_week: u8) -> (NaiveDate, NaiveDate) {
+    let first_day_of_week = Weekday::try_from(first_day_of_week).unwrap_or(Weekday::Sun);
+    let first = get_calendar_first(
+        date_selected.year(),
+        date_selected.month(),
+        first_day_of_week,
+    );
+    let last = first
+        .checked_add_days(Days::new(CALENDAR_DAYS as u64 - 1))
+        .unwrap_or(first);
+    (first, last)
+}
+
+/// Week number of the grid row starting on `week_start`
+///
+/// Weeks starting on Monday use ISO 8601 numbering (week 1 holds the first
+/// Thursday of the year); other week starts count from the week containing
+/// January 1st, as in North America and the Middle East.
+fn week_number(week_start: NaiveDate) -> u32 {
+    if week_start.weekday() == Weekday::Mon {
+        return week_start.iso_week().week();
+    }
+    // A row that reaches into January belongs to the new year
+    let year = (week_start + Days::new(6)).year();
+    let week_one = get_calendar_first(year, 1, week_start.weekday());
+    ((week_start - week_one).num_days() / 7) as u32 + 1
+}
+
 /// Creates an ICU DateTime from a chrono date and time components
 pub fn create_datetime<D: Datelike, T: Timelike>(
     date: &D,
@@ -120,12 +438,17 @@ pub fn create_datetime<D: Datelike, T: Timelike>(
     }
 }
 
-/// Creates a styled button for a calendar day
+/// Creates a styled button for a calendar day, with the day in the secondary
+/// calendar and markers (moon phase glyph, event dot) below the number.
+/// Public holidays have an accent-colored number.
 fn date_button(
     day: u32,
     is_month: bool,
     is_day: bool,
     is_today: bool,
+    is_holiday: bool,
+    secondary_day: Option<String>,
+    markers: Vec<&'static str>,
 ) -> Button<'static, CalendarMessage> {
     let style = if is_day {
         button::ButtonClass::Suggested
@@ -135,14 +458,30 @@ fn date_button(
         button::ButtonClass::Text
     };
 
-    let button = button::custom(
-        text::body(format!("{day}"))
-            .apply(container)
-            .center(Length::Fill),
-    )
-    .class(style)
-    .height(Length::Fixed(DAY_BUTTON_SIZE))
-    .width(Length::Fixed(DAY_BUTTON_SIZE));
+    let number = text::body(format!("{day}"));
+    // Not on the selected day: accent text on the accent background
+    let number = if is_holiday && !is_day {
+        number.class(cosmic::theme::Text::Accent)
+    } else {
+        number
+    };
+
+    let mut label = column![number].align_x(Alignment::Center);
+    if let Some(secondary_day) = secondary_day {
+        label = label.push(text::caption(secondary_day).size(SECONDARY_DAY_SIZE));
+    }
+    if !markers.is_empty() {
+        let markers: Vec<Element<'static, CalendarMessage>> = markers
+            .into_iter()
+            .map(|marker| text::body(marker).size(MARKER_SIZE).into())
+            .collect();
+        label = label.push(Row::with_children(markers).spacing(2));
+    }
+
+    let button = button::custom(label.apply(container).center(Length::Fill))
+        .class(style)
+        .height(Length::Fixed(DAY_BUTTON_SIZE))
+        .width(Length::Fixed(DAY_BUTTON_SIZE));
 
     if is_month {
         button.on_press(CalendarMessage::SelectDay(day))
@@ -156,11 +495,11 @@ fn calendar_grid<'a, T: Timelike>(
     locale: &'a Locale,
     calendar_state: &CalendarState,
     now: &T,
-    first_day_of_week: u8,
+    options: &GridOptions,
 ) -> Grid<'a, CalendarMessage> {
     let mut calendar: Grid<'a, CalendarMessage> = grid().width(Length::Fill);
     let mut first_day_of_week =
-        chrono::Weekday::try_from(first_day_of_week).unwrap_or(chrono::Weekday::Sun);
+        chrono::Weekday::try_from(options.first_day_of_week).unwrap_or(chrono::Weekday::Sun);
 
     let first_day = get_calendar_first(
         calendar_state.date_selected.year(),
@@ -173,7 +512,22 @@ fn calendar_grid<'a, T: Timelike>(
     // Create formatter once for this render (following cosmic-applet-time pattern)
     let prefs = DateTimeFormatterPreferences::from(locale.clone());
     let weekday_formatter = DateTimeFormatter::try_new(prefs, fieldsets::E::short()).unwrap();
+    let secondary_day_formatter = options
+        .secondary_calendar
+        .as_ref()
+        .filter(|secondary| secondary.show_days)
+        .map(|secondary| {
+            let prefs = DateTimeFormatterPreferences::from(secondary.locale.clone());
+            DateTimeFormatter::try_new(prefs, fieldsets::D::short()).unwrap()
+        });
 
+    if options.week_numbers {
+        calendar = calendar.push(
+            text::caption(fl!("calendar-week"))
+                .apply(container)
+                .center_x(Length::Fixed(WEEK_NUMBER_WIDTH)),
+        );
+    }
     for date in day_iter.take(7) {
         let datetime = create_datetime(&date, now);
         calendar = calendar.push(
@@ -192,33 +546,421 @@ fn calendar_grid<'a, T: Timelike>(
         }
 
         let date = day_iter.next().unwrap();
+        if options.week_numbers && i % 7 == 0 {
+            calendar = calendar.push(
+                text::caption(week_number(date).to_string())
+                    .apply(container)
+                    .center_x(Length::Fixed(WEEK_NUMBER_WIDTH))
+                    .center_y(Length::Fixed(DAY_BUTTON_SIZE)),
+            );
+        }
         let is_month = date.month() == calendar_state.date_selected.month()
             && date.year_ce() == calendar_state.date_selected.year_ce();
         let is_day = date.day() == calendar_state.date_selected.day() && is_month;
         let is_today = date == calendar_state.date_today;
 
-        calendar = calendar.push(date_button(date.day(), is_month, is_day, is_today));
+        let is_holiday = calendar_state.holidays_on(date).next().is_some();
+        let secondary_day = secondary_day_formatter
+            .as_ref()
+            .map(|formatter| formatter.format(&create_datetime(&date, now)).to_string());
+        let moon = options
+            .moon_offset
+            .and_then(|offset| principal_phase(date, &offset));
+        let markers = moon
+            .map(MoonPhase::glyph)
+            .into_iter()
+            .chain(
+                calendar_state
+                    .agenda
+                    .has_events_on(date)
+                    .then_some(EVENT_DOT),
+            )
+            .collect();
+
+        calendar = calendar.push(date_button(
+            date.day(),
+            is_month,
+            is_day,
+            is_today,
+            is_holiday,
+            secondary_day,
+            markers,
+        ));
     }
 
     calendar
 }
 
+/// A month of the year overview: title and day numbers, today in accent
+/// color; pressing it opens the month
+fn mini_month<'a>(
+    title: String,
+    year: i32,
+    month: u32,
+    today: NaiveDate,
+    first_day_of_week: Weekday,
+    weekday_initials: &[String],
+) -> Element<'a, CalendarMessage> {
+    let cell = |label: String, is_today: bool| {
+        let label = text::caption(label).size(MINI_DAY_SIZE);
+        let label = if is_today {
+            label.class(cosmic::theme::Text::Accent)
+        } else {
+            label
+        };
+        label
+            .apply(container)
+            .center_x(Length::Fixed(MINI_DAY_WIDTH))
+    };
+
+    let mut weeks = widget::column().push(text::body(title));
+    let mut header = widget::row();
+    for initial in weekday_initials {
+        header = header.push(cell(initial.clone(), false));
+    }
+    weeks = weeks.push(header);
+
+    let mut days = get_calendar_first(year, month, first_day_of_week).iter_days();
+    for _ in 0..CALENDAR_DAYS / 7 {
+        let mut week = widget::row();
+        for date in days.by_ref().take(7) {
+            // Days of the neighbouring months stay blank
+            week = week.push(if date.month() == month {
+                cell(date.day().to_string(), date == today)
+            } else {
+                cell(String::new(), false)
+            });
+        }
+        weeks = weeks.push(week);
+    }
+
+    button::custom(weeks)
+        .class(button::ButtonClass::Text)
+        .padding(4)
+        .on_press(CalendarMessage::ShowMonth(month))
+        .into()
+}
+
+/// Year overview: the twelve months of the selected date's year, year
+/// navigation and the "jump to date" entry
+fn view_year<'a, T: Timelike>(
+    locale: &'a Locale,
+    calendar_state: &CalendarState,
+    now: &T,
+    options: &GridOptions,
+) -> Element<'a, CalendarMessage> {
+    let year = calendar_state.date_selected.year();
+    let first_day_of_week = Weekday::try_from(options.first_day_of_week).unwrap_or(Weekday::Sun);
+
+    // Create formatters once for this render (following cosmic-applet-time pattern)
+    let prefs = gregorian_prefs(locale);
+    let year_formatter =
+        FixedCalendarDateTimeFormatter::<Gregorian, _>::try_new(prefs, fieldsets::Y::medium())
+            .unwrap();
+    let month_formatter =
+        FixedCalendarDateTimeFormatter::<Gregorian, _>::try_new(prefs, fieldsets::M::long())
+            .unwrap();
+    let weekday_formatter = DateTimeFormatter::try_new(prefs, fieldsets::E::short()).unwrap();
+
+    let first_of_year = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let title = text(
+        year_formatter
+            .format(&create_datetime(&first_of_year, now))
+            .to_string(),
+    )
+    .size(18);
+    let year_controls = row![
+        button::icon(widget::icon::from_name(icons::navigation::PREVIOUS))
+            .padding(8)
+            .on_press(CalendarMessage::PreviousYear),
+        button::icon(widget::icon::from_name(icons::navigation::NEXT))
+            .padding(8)
+            .on_press(CalendarMessage::NextYear)
+    ]
+    .spacing(8);
+
+    // Narrow weekday headers: first letter of the short names
+    let weekday_initials: Vec<String> = get_calendar_first(year, 1, first_day_of_week)
+        .iter_days()
+        .take(7)
+        .map(|date| {
+            let name = weekday_formatter
+                .format(&create_datetime(&date, now))
+                .to_string();
+            name.chars().next().map(String::from).unwrap_or_default()
+        })
+        .collect();
+
+    let mut months = widget::column().spacing(8);
+    let mut months_row = widget::row().spacing(8);
+    for month in 1..=12 {
+        let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+        let title = month_formatter
+            .format(&create_datetime(&first, now))
+            .to_string();
+        months_row = months_row.push(mini_month(
+            title,
+            year,
+            month,
+            calendar_state.date_today,
+            first_day_of_week,
+            &weekday_initials,
+        ));
+        if month % YEAR_COLUMNS == 0 {
+            months = months.push(months_row);
+            months_row = widget::row().spacing(8);
+        }
+    }
+
+    let mut jump = widget::column().push(
+        row![
+            widget::text_input(
+                fl!("calendar-jump-placeholder"),
+                calendar_state.jump_query.clone()
+            )
+            .on_input(CalendarMessage::JumpInput)
+            .width(Length::Fill),
+            button::standard(fl!("calendar-jump")).on_press(CalendarMessage::JumpToDate),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    );
+    if calendar_state.jump_invalid {
+        jump = jump.push(text::caption(fl!("calendar-jump-invalid")));
+    }
+
+    column![
+        row![
+            title,
+            widget::Space::with_width(Length::Fill),
+            year_controls
+        ]
+        .align_y(Alignment::Center)
+        .padding(HEADER_PADDING),
+        container(months).center_x(Length::Fill),
+        widget::divider::horizontal::default(),
+        container(jump.spacing(4)).padding([8, 20]),
+    ]
+    .into()
+}
+
+/// Summary of an occurrence, or "(No title)"
+fn summary(occurrence: &Occurrence) -> String {
+    match occurrence.summary.trim() {
+        "" => fl!("event-untitled"),
+        summary => summary.to_string(),
+    }
+}
+
+/// When an occurrence takes place on `date`: "All day", or its times (only
+/// the part on `date` is timed)
+fn time_on(
+    occurrence: &Occurrence,
+    date: NaiveDate,
+    format_time: impl Fn(&chrono::DateTime<FixedOffset>) -> String,
+) -> String {
+    match occurrence.time {
+        OccurrenceTime::AllDay { .. } => fl!("event-all-day"),
+        OccurrenceTime::Timed { start, end } => {
+            match (start.date_naive() == date, occurrence.last_day() == date) {
+                (true, true) if start == end => format_time(&start),
+                (true, true) => format!("{} – {}", format_time(&start), format_time(&end)),
+                (true, false) => format!("{} –", format_time(&start)),
+                (false, true) => format!("– {}", format_time(&end)),
+                (false, false) => fl!("event-all-day"),
+            }
+        }
+    }
+}
+
+/// Hours and minutes in the locale's format
+fn time_formatter(locale: &Locale) -> DateTimeFormatter<fieldsets::T> {
+    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    DateTimeFormatter::try_new(
+        prefs,
+        fieldsets::T::medium().with_time_precision(TimePrecision::Minute),
+    )
+    .unwrap()
+}
+
+/// Holidays and events of the selected day: for events, time (or "All
+/// day"), summary and location
+fn view_day_events<'a>(
+    locale: &Locale,
+    calendar_state: &CalendarState,
+) -> Element<'a, CalendarMessage> {
+    let time_formatter = time_formatter(locale);
+    let format_time = |time: &chrono::DateTime<FixedOffset>| {
+        time_formatter
+            .format(&create_datetime(time, time))
+            .to_string()
+    };
+
+    let selected = calendar_state.date_selected;
+    let mut events = widget::column().spacing(8);
+    for holiday in calendar_state.holidays_on(selected) {
+        events = events.push(
+            column![
+                text::body(holiday.name.clone()),
+                text::caption(fl!("holiday-public"))
+            ]
+            .spacing(2),
+        );
+    }
+    for occurrence in calendar_state.agenda.on(selected) {
+        let when = time_on(occurrence, selected, &format_time);
+        let details = match &occurrence.location {
+            Some(location) => format!("{when} · {location}"),
+            None => when,
+        };
+        events = events
+            .push(column![text::body(summary(occurrence)), text::caption(details)].spacing(2));
+    }
+    let is_holiday = calendar_state.holidays_on(selected).next().is_some();
+    if !calendar_state.agenda.has_events_on(selected) && !is_holiday {
+        events = events.push(text::caption(fl!("event-none")));
+    }
+    if let Some(err) = &calendar_state.caldav.error {
+        events = events.push(text::caption(fl!(
+            "caldav-sync-failed",
+            error = err.to_string()
+        )));
+    }
+    events.into()
+}
+
+/// Details of an agenda entry: location, description and attendees
+fn event_details<'a>(occurrence: &Occurrence) -> Element<'a, CalendarMessage> {
+    let mut details = widget::column().spacing(4);
+    if let Some(location) = &occurrence.location {
+        details = details.push(text::caption(location.clone()));
+    }
+    if let Some(description) = &occurrence.description {
+        details = details.push(text::body(description.clone()));
+    }
+    if !occurrence.attendees.is_empty() {
+        details = details.push(text::caption(fl!(
+            "event-attendees",
+            names = occurrence.attendees.join(", ")
+        )));
+    }
+    if occurrence.location.is_none()
+        && occurrence.description.is_none()
+        && occurrence.attendees.is_empty()
+    {
+        details = details.push(text::caption(fl!("event-no-details")));
+    }
+    details.into()
+}
+
+/// Agenda: the events of the next `agenda_days` days, grouped under a header
+/// per day, in a scrolling list; clicking an event shows its details
+fn view_agenda<'a, T: Timelike>(
+    locale: &'a Locale,
+    calendar_state: &CalendarState,
+    now: &T,
+    options: &GridOptions,
+) -> Element<'a, CalendarMessage> {
+    // Create formatters once for this render (following cosmic-applet-time pattern)
+    let prefs = gregorian_prefs(locale);
+    let day_formatter = DateTimeFormatter::try_new(prefs, fieldsets::MDE::long()).unwrap();
+    let time_formatter = time_formatter(locale);
+    let format_time = |time: &chrono::DateTime<FixedOffset>| {
+        time_formatter
+            .format(&create_datetime(time, time))
+            .to_string()
+    };
+
+    let today = calendar_state.date_today;
+    let agenda_days = options.agenda_days.clamp(1, MAX_AGENDA_DAYS);
+    let upcoming = calendar_state.agenda.upcoming();
+    let mut days = widget::column().spacing(12);
+    for date in today.iter_days().take(agenda_days as usize) {
+        let mut entries = upcoming
+            .iter()
+            .enumerate()
+            .filter(|(_, occurrence)| occurrence.is_on(date))
+            .peekable();
+        if entries.peek().is_none() {
+            continue;
+        }
+
+        let title = day_formatter
+            .format(&create_datetime(&date, now))
+            .to_string();
+        let title = if date == today {
+            text::heading(title).class(cosmic::theme::Text::Accent)
+        } else {
+            text::heading(title)
+        };
+        let mut day = widget::column().push(title).spacing(4);
+        for (index, occurrence) in entries {
+            let entry = column![
+                text::body(summary(occurrence)),
+                text::caption(time_on(occurrence, date, &format_time)),
+            ]
+            .spacing(2);
+            day = day.push(
+                button::custom(entry)
+                    .class(button::ButtonClass::Text)
+                    .width(Length::Fill)
+                    .padding([4, 8])
+                    .on_press(CalendarMessage::ToggleEventDetails(index)),
+            );
+            if calendar_state.event_details.as_ref() == Some(occurrence) {
+                day = day.push(container(event_details(occurrence)).padding([0, 16]));
+            }
+        }
+        days = days.push(day);
+    }
+    if upcoming.is_empty() {
+        days = days.push(text::caption(fl!("agenda-empty", days = agenda_days)));
+    }
+    if let Some(err) = &calendar_state.caldav.error {
+        days = days.push(text::caption(fl!(
+            "caldav-sync-failed",
+            error = err.to_string()
+        )));
+    }
+
+    column![
+        row![
+            text(fl!("agenda-title")).size(18),
+            widget::Space::with_width(Length::Fill),
+            button::icon(widget::icon::from_name(icons::calendar::MONTH))
+                .padding(8)
+                .on_press(CalendarMessage::CloseAgenda),
+        ]
+        .align_y(Alignment::Center)
+        .padding(HEADER_PADDING),
+        container(widget::scrollable(container(days).padding([0, 20]))).max_height(AGENDA_HEIGHT),
+    ]
+    .into()
+}
+
 // ============================================================================
 // Public View Function
 // ============================================================================
 
-/// Renders the complete calendar view with header, navigation, and day grid
+/// Renders the complete calendar view with header, navigation, and day grid,
+/// or the year overview, or the agenda
 /// Follows cosmic-applet-time pattern: formatters created once per render
 pub fn view_calendar<'a, T: Timelike>(
     locale: &'a Locale,
     calendar_state: &CalendarState,
     now: &T,
-    first_day_of_week: u8,
+    options: &GridOptions,
+    sun: Option<&SunTimes>,
 ) -> Element<'a, CalendarMessage> {
+    match calendar_state.view {
+        CalendarView::Month => {}
+        CalendarView::Year => return view_year(locale, calendar_state, now, options),
+        CalendarView::Agenda => return view_agenda(locale, calendar_state, now, options),
+    }
     let datetime = create_datetime(&calendar_state.date_selected, now);
 
     // Create formatters once for this render (following cosmic-applet-time pattern)
-    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    let prefs = gregorian_prefs(locale);
     let date_formatter = DateTimeFormatter::try_new(prefs, fieldsets::YMD::long()).unwrap();
     let weekday_formatter = DateTimeFormatter::try_new(prefs, fieldsets::E::long()).unwrap();
 
@@ -226,7 +968,20 @@ pub fn view_calendar<'a, T: Timelike>(
 
     let day_of_week = text::body(weekday_formatter.format(&datetime).to_string());
 
+    let mut header = column![date, day_of_week];
+    if let Some(secondary) = &options.secondary_calendar {
+        let prefs = DateTimeFormatterPreferences::from(secondary.locale.clone());
+        let secondary_formatter =
+            DateTimeFormatter::try_new(prefs, fieldsets::YMD::long()).unwrap();
+        header = header.push(text::caption(
+            secondary_formatter.format(&datetime).to_string(),
+        ));
+    }
+
     let month_controls = row![
+        button::icon(widget::icon::from_name(icons::calendar::AGENDA))
+            .padding(8)
+            .on_press(CalendarMessage::ShowAgenda),
         button::icon(widget::icon::from_name(icons::navigation::PREVIOUS))
             .padding(8)
             .on_press(CalendarMessage::PreviousMonth),
@@ -236,17 +991,106 @@ pub fn view_calendar<'a, T: Timelike>(
     ]
     .spacing(8);
 
-    let calendar = calendar_grid(locale, calendar_state, now, first_day_of_week);
+    let calendar = calendar_grid(locale, calendar_state, now, options);
 
-    column![
+    // The header date opens the year overview
+    let header = button::custom(header)
+        .class(button::ButtonClass::Text)
+        .padding(0)
+        .on_press(CalendarMessage::ShowYear);
+
+    let mut content = column![
         row![
-            column![date, day_of_week],
+            header,
             widget::Space::with_width(Length::Fill),
             month_controls,
         ]
         .align_y(Alignment::Center)
         .padding(HEADER_PADDING),
         calendar.padding([0, 12].into()),
-    ]
-    .into()
+    ];
+    // Day detail of the selected date: holidays and events, then the sun
+    // (needs a weather location)
+    let is_holiday = calendar_state
+        .holidays_on(calendar_state.date_selected)
+        .next()
+        .is_some();
+    if !calendar_state.agenda.is_empty() || calendar_state.caldav.error.is_some() || is_holiday {
+        content = content
+            .push(widget::divider::horizontal::default())
+            .push(container(view_day_events(locale, calendar_state)).padding([8, 20]));
+    }
+    if let Some(sun) = sun {
+        content = content
+            .push(widget::divider::horizontal::default())
+            .push(container(view_sun_times(locale, sun)).padding([8, 20]));
+    }
+    content.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn secondary(configured: &str, locale: &str) -> Option<SecondaryCalendar> {
+        SecondaryCalendar::new(configured, &locale.parse().unwrap())
+    }
+
+    #[test]
+    fn test_secondary_calendar() {
+        // From the locale's -u-ca- extension
+        let hebrew = secondary("", "he-IL-u-ca-hebrew").unwrap();
+        assert!(hebrew.show_days);
+        assert_eq!(hebrew.locale.to_string(), "he-IL-u-ca-hebrew");
+        assert!(secondary("", "en-US").is_none());
+        // The config wins over the locale
+        let hijri = secondary("islamic-umalqura", "ar-SA-u-ca-gregory").unwrap();
+        assert_eq!(hijri.locale.to_string(), "ar-SA-u-ca-islamic-umalqura");
+        assert!(secondary("gregory", "fa-IR-u-ca-persian").is_none());
+        // Same days as the Gregorian calendar: header only
+        assert!(!secondary("Japanese", "ja-JP").unwrap().show_days);
+        assert!(secondary("mayan", "en-US").is_none());
+    }
+
+    #[test]
+    fn test_parse_jump() {
+        let today = date(2026, 10, 18);
+        assert_eq!(parse_jump(" 2027-04-30 ", today), Some(date(2027, 4, 30)));
+        assert_eq!(parse_jump("2027-04", today), Some(date(2027, 4, 1)));
+        assert_eq!(parse_jump("+6m", today), Some(date(2027, 4, 18)));
+        assert_eq!(parse_jump("-2w", today), Some(date(2026, 10, 4)));
+        assert_eq!(parse_jump("+10d", today), Some(date(2026, 10, 28)));
+        assert_eq!(parse_jump("+1Y", today), Some(date(2027, 10, 18)));
+        assert_eq!(parse_jump("2027-02-30", today), None);
+        assert_eq!(parse_jump("+m", today), None);
+        assert_eq!(parse_jump("tomorrow", today), None);
+        assert_eq!(clamp_day(2027, 2, 31), Some(date(2027, 2, 28)));
+    }
+
+    #[test]
+    fn test_iso_week_numbers() {
+        // 2021-01-01 is a Friday: its row still belongs to week 53 of 2020
+        assert_eq!(week_number(date(2020, 12, 28)), 53);
+        assert_eq!(week_number(date(2021, 1, 4)), 1);
+        // 2024-12-30 starts week 1 of 2025
+        assert_eq!(week_number(date(2024, 12, 30)), 1);
+        assert_eq!(week_number(date(2025, 6, 16)), 25);
+    }
+
+    #[test]
+    fn test_sunday_week_numbers() {
+        // Week 1 contains January 1st, even when it starts in December
+        assert_eq!(week_number(date(2024, 12, 29)), 1);
+        assert_eq!(week_number(date(2025, 1, 5)), 2);
+        assert_eq!(week_number(date(2025, 12, 21)), 52);
+        assert_eq!(week_number(date(2025, 12, 28)), 1);
+        // Saturday weeks (Middle East)
+        assert_eq!(week_number(date(2022, 1, 1)), 1);
+        assert_eq!(week_number(date(2022, 12, 24)), 52);
+        assert_eq!(week_number(date(2022, 12, 31)), 1);
+    }
 }
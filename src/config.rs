@@ -4,6 +4,48 @@ use cosmic_config::CosmicConfigEntry;
 use cosmic_config_derive::CosmicConfigEntry as CosmicConfigEntryDerive;
 use serde::{Deserialize, Serialize};
 
+use crate::alarm::Alarm;
+use crate::units::{PrecipitationUnit, PressureUnit, TemperatureUnit, WindSpeedUnit};
+use crate::weather_provider::Coordinates;
+
+/// How an active countdown is shown in the panel button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PanelTimerDisplay {
+    /// Only in the popup
+    Hidden,
+    /// Remaining time next to the clock
+    #[default]
+    BesideClock,
+    /// Remaining time in place of the clock while a countdown is active
+    InsteadOfClock,
+}
+
+/// What of the current weather is shown in the panel button, before the clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PanelWeatherDisplay {
+    /// Only in the popup
+    #[default]
+    Hidden,
+    /// Condition icon
+    Icon,
+    /// Current temperature
+    Temperature,
+    /// Condition icon and current temperature
+    IconAndTemperature,
+}
+
+impl PanelWeatherDisplay {
+    /// Check if the condition icon is shown
+    pub fn shows_icon(self) -> bool {
+        matches!(self, Self::Icon | Self::IconAndTemperature)
+    }
+
+    /// Check if the temperature is shown
+    pub fn shows_temperature(self) -> bool {
+        matches!(self, Self::Temperature | Self::IconAndTemperature)
+    }
+}
+
 /// Configuration for the time applet (from system)
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, CosmicConfigEntryDerive)]
 #[version = 1]
@@ -26,6 +68,149 @@ pub struct TimeAppletConfig {
     /// Custom strftime format string (overrides other settings if set).
     #[serde(default)]
     pub format_strftime: String,
+    /// Pomodoro work phase length in minutes.
+    #[serde(default = "default_pomodoro_work_minutes")]
+    pub pomodoro_work_minutes: u32,
+    /// Pomodoro short break length in minutes.
+    #[serde(default = "default_pomodoro_short_break_minutes")]
+    pub pomodoro_short_break_minutes: u32,
+    /// Pomodoro long break length in minutes.
+    #[serde(default = "default_pomodoro_long_break_minutes")]
+    pub pomodoro_long_break_minutes: u32,
+    /// Number of work phases before a long break.
+    #[serde(default = "default_pomodoro_cycles_before_long_break")]
+    pub pomodoro_cycles_before_long_break: u32,
+    /// Play a sound when a timer fires.
+    #[serde(default = "default_true")]
+    pub alarm_sound_enabled: bool,
+    /// Alarm volume in percent (0-100).
+    #[serde(default = "default_alarm_volume")]
+    pub alarm_volume: u8,
+    /// Repeat countdown and alarm sounds until their notification is dismissed.
+    #[serde(default = "default_true")]
+    pub alarm_loop: bool,
+    /// Sound played when a countdown finishes (file name in the sounds
+    /// directory or absolute path; empty = silent).
+    #[serde(default = "default_alarm_sound")]
+    pub countdown_sound: String,
+    /// Sound played when a Pomodoro phase finishes (same rules as above).
+    #[serde(default = "default_alarm_sound")]
+    pub pomodoro_sound: String,
+    /// Sound played when a wall-clock alarm rings (same rules as above).
+    #[serde(default = "default_alarm_sound")]
+    pub alarm_sound: String,
+    /// How an active countdown is shown in the panel button.
+    #[serde(default)]
+    pub panel_timer_display: PanelTimerDisplay,
+    /// Show a progress ring next to the countdown in the panel.
+    #[serde(default)]
+    pub panel_timer_progress: bool,
+    /// Wall-clock alarms, edited from the Timer tab.
+    #[serde(default)]
+    pub alarms: Vec<Alarm>,
+    /// ID for the next new alarm; IDs of deleted alarms are not reused.
+    #[serde(default)]
+    pub next_alarm_id: u32,
+    /// Latitude of the weather location in decimal degrees.
+    #[serde(default)]
+    pub weather_latitude: Option<f64>,
+    /// Longitude of the weather location in decimal degrees.
+    #[serde(default)]
+    pub weather_longitude: Option<f64>,
+    /// Display name of the weather location (e.g. a city picked in the
+    /// Weather tab). Empty shows the coordinates.
+    #[serde(default)]
+    pub weather_location_name: String,
+    /// Base URL of the Open-Meteo compatible weather server.
+    #[serde(default = "default_weather_base_url")]
+    pub weather_base_url: String,
+    /// Severe weather alerts feed (Atom with CAP fields, or a CAP message).
+    /// `{latitude}` and `{longitude}` are replaced by the location; empty
+    /// disables alerts.
+    #[serde(default)]
+    pub weather_alerts_url: String,
+    /// Temperature unit of the weather (unset follows the locale).
+    #[serde(default)]
+    pub weather_temperature_unit: Option<TemperatureUnit>,
+    /// Wind speed unit of the weather (unset follows the locale).
+    #[serde(default)]
+    pub weather_wind_speed_unit: Option<WindSpeedUnit>,
+    /// Pressure unit of the weather (unset follows the locale).
+    #[serde(default)]
+    pub weather_pressure_unit: Option<PressureUnit>,
+    /// Precipitation unit of the weather (unset follows the locale).
+    #[serde(default)]
+    pub weather_precipitation_unit: Option<PrecipitationUnit>,
+    /// Current weather shown in the panel button.
+    #[serde(default)]
+    pub panel_weather_display: PanelWeatherDisplay,
+    /// Mark new moons, quarters and full moons in the calendar grid.
+    #[serde(default)]
+    pub show_moon_phases: bool,
+    /// Show a week number column in the calendar grid: ISO 8601 weeks when
+    /// weeks start on Monday, otherwise week 1 is the one with January 1st.
+    #[serde(default)]
+    pub show_week_numbers: bool,
+    /// Calendar system shown in small print next to the Gregorian dates (CLDR
+    /// identifier such as `hebrew`, `islamic-umalqura`, `persian`, `chinese`,
+    /// `japanese` or `buddhist`); empty follows the locale's `-u-ca-`
+    /// extension, `gregory` shows none.
+    #[serde(default)]
+    pub secondary_calendar: String,
+    /// Region whose public holidays are marked (ISO 3166 code such as `US`
+    /// or `DE-BY`); empty follows the locale's region.
+    #[serde(default)]
+    pub holiday_region: String,
+    /// iCalendar files shown in the calendar, or directories of them
+    /// (`~/` is expanded).
+    #[serde(default)]
+    pub calendar_ics_paths: Vec<String>,
+    /// CalDAV server, principal or calendar home URL; empty disables sync.
+    /// The password is read from the keyring, never stored here.
+    #[serde(default)]
+    pub caldav_url: String,
+    /// CalDAV user name (empty: no authentication)
+    #[serde(default)]
+    pub caldav_username: String,
+    /// Days listed by the calendar's agenda, today included (at most 366).
+    #[serde(default = "default_agenda_days")]
+    pub agenda_days: u32,
+}
+
+fn default_pomodoro_work_minutes() -> u32 {
+    25
+}
+
+fn default_pomodoro_short_break_minutes() -> u32 {
+    5
+}
+
+fn default_pomodoro_long_break_minutes() -> u32 {
+    15
+}
+
+fn default_pomodoro_cycles_before_long_break() -> u32 {
+    4
+}
+
+fn default_agenda_days() -> u32 {
+    14
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_alarm_volume() -> u8 {
+    80
+}
+
+fn default_alarm_sound() -> String {
+    "alarm.ogg".to_string()
+}
+
+fn default_weather_base_url() -> String {
+    crate::weather_provider::DEFAULT_BASE_URL.to_string()
 }
 
 impl Default for TimeAppletConfig {
@@ -37,6 +222,38 @@ impl Default for TimeAppletConfig {
             show_seconds: false,
             first_day_of_week: 0,
             format_strftime: String::new(),
+            pomodoro_work_minutes: default_pomodoro_work_minutes(),
+            pomodoro_short_break_minutes: default_pomodoro_short_break_minutes(),
+            pomodoro_long_break_minutes: default_pomodoro_long_break_minutes(),
+            pomodoro_cycles_before_long_break: default_pomodoro_cycles_before_long_break(),
+            alarm_sound_enabled: true,
+            alarm_volume: default_alarm_volume(),
+            alarm_loop: true,
+            countdown_sound: default_alarm_sound(),
+            pomodoro_sound: default_alarm_sound(),
+            alarm_sound: default_alarm_sound(),
+            panel_timer_display: PanelTimerDisplay::default(),
+            panel_timer_progress: false,
+            alarms: Vec::new(),
+            next_alarm_id: 1,
+            weather_latitude: None,
+            weather_longitude: None,
+            weather_location_name: String::new(),
+            weather_base_url: default_weather_base_url(),
+            weather_alerts_url: String::new(),
+            weather_temperature_unit: None,
+            weather_wind_speed_unit: None,
+            weather_pressure_unit: None,
+            weather_precipitation_unit: None,
+            panel_weather_display: PanelWeatherDisplay::default(),
+            show_moon_phases: false,
+            show_week_numbers: false,
+            secondary_calendar: String::new(),
+            holiday_region: String::new(),
+            calendar_ics_paths: Vec::new(),
+            caldav_url: String::new(),
+            caldav_username: String::new(),
+            agenda_days: default_agenda_days(),
         }
     }
 }
@@ -70,4 +287,18 @@ impl TimeAppletConfig {
     pub fn is_24_hour(&self) -> bool {
         self.military_time
     }
+
+    /// Weather location, if both coordinates are set and in range
+    pub fn weather_coordinates(&self) -> Option<Coordinates> {
+        let latitude = self
+            .weather_latitude
+            .filter(|lat| (-90.0..=90.0).contains(lat))?;
+        let longitude = self
+            .weather_longitude
+            .filter(|lon| (-180.0..=180.0).contains(lon))?;
+        Some(Coordinates {
+            latitude,
+            longitude,
+        })
+    }
 }
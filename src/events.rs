@@ -0,0 +1,161 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Calendar events
+//!
+//! Loads the events of the iCalendar files listed in `calendar_ics_paths`
+//! (files, or directories whose `.ics` files are all read) and keeps the
+//! occurrences of the dates the calendar grid shows, and of the coming days
+//! the agenda lists, so rendering never expands recurrences. Files are read
+//! again each time the popup opens; CalDAV events (`caldav.rs`) are kept
+//! alongside them.
+
+use chrono::{Days, NaiveDate, TimeZone};
+use std::path::{Path, PathBuf};
+
+use crate::ics::{Event, Occurrence, parse_calendar};
+
+/// Most days the agenda lists (a year); a larger `agenda_days` is capped
+pub const MAX_AGENDA_DAYS: u32 = 366;
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn is_ics(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ics"))
+}
+
+/// The `.ics` files of a configured path (sorted, for a stable order)
+async fn ics_files(path: PathBuf) -> Vec<PathBuf> {
+    let Ok(mut entries) = tokio::fs::read_dir(&path).await else {
+        return vec![path];
+    };
+    let mut files = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if is_ics(&entry.path()) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Read the events of all configured paths
+///
+/// Unreadable files are logged and skipped: the other calendars still show.
+pub async fn load(paths: Vec<String>) -> Vec<Event> {
+    let mut events = Vec::new();
+    for path in paths.iter().filter(|path| !path.trim().is_empty()) {
+        for file in ics_files(expand_home(path.trim())).await {
+            match tokio::fs::read_to_string(&file).await {
+                Ok(text) => {
+                    let parsed = parse_calendar(&text);
+                    tracing::debug!("[Calendar] {} events in {}", parsed.len(), file.display());
+                    events.extend(parsed);
+                }
+                Err(err) => {
+                    tracing::warn!("[Calendar] Cannot read {}: {}", file.display(), err);
+                }
+            }
+        }
+    }
+    events
+}
+
+// ============================================================================
+// Agenda
+// ============================================================================
+
+/// Where events come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSource {
+    /// Local iCalendar files (`calendar_ics_paths`)
+    Files,
+    /// Calendars synced from the CalDAV server
+    CalDav,
+}
+
+/// Loaded events and their occurrences on the visible and upcoming dates
+#[derive(Debug, Clone, Default)]
+pub struct Agenda {
+    files: Vec<Event>,
+    caldav: Vec<Event>,
+    /// Occurrences of the expanded dates, in display order
+    occurrences: Vec<Occurrence>,
+    /// Occurrences of the upcoming days, in display order
+    upcoming: Vec<Occurrence>,
+}
+
+impl Agenda {
+    /// Replace the events of `source` (occurrences are stale until the next
+    /// `expand`)
+    pub fn set_events(&mut self, source: EventSource, events: Vec<Event>) {
+        match source {
+            EventSource::Files => self.files = events,
+            EventSource::CalDav => self.caldav = events,
+        }
+        self.occurrences.clear();
+        self.upcoming.clear();
+    }
+
+    /// Check if any event was loaded
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.caldav.is_empty()
+    }
+
+    /// Occurrences of all events on the dates `first..=last`, in `tz`
+    fn occurrences<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Vec<Occurrence> {
+        let mut occurrences: Vec<Occurrence> = self
+            .files
+            .iter()
+            .chain(&self.caldav)
+            .flat_map(|event| event.occurrences(first, last, tz))
+            .collect();
+        occurrences.sort_by_key(Occurrence::sort_key);
+        occurrences
+    }
+
+    /// Compute the occurrences on the dates `first..=last`, in `tz`
+    pub fn expand<Tz: TimeZone>(&mut self, first: NaiveDate, last: NaiveDate, tz: &Tz) {
+        self.occurrences = self.occurrences(first, last, tz);
+    }
+
+    /// Compute the occurrences of the `days` days from `today` (1 to
+    /// `MAX_AGENDA_DAYS`), in `tz`
+    pub fn expand_upcoming<Tz: TimeZone>(&mut self, today: NaiveDate, days: u32, tz: &Tz) {
+        let days = days.clamp(1, MAX_AGENDA_DAYS);
+        let last = today
+            .checked_add_days(Days::new(u64::from(days - 1)))
+            .unwrap_or(today);
+        self.upcoming = self.occurrences(today, last, tz);
+    }
+
+    /// Occurrences of the upcoming days, by first day then start time (an
+    /// occurrence that started before today comes first)
+    pub fn upcoming(&self) -> &[Occurrence] {
+        &self.upcoming
+    }
+
+    /// Occurrences taking place (at least partly) on `date`
+    pub fn on(&self, date: NaiveDate) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences
+            .iter()
+            .filter(move |occurrence| occurrence.is_on(date))
+    }
+
+    /// Check if anything takes place on `date`
+    pub fn has_events_on(&self, date: NaiveDate) -> bool {
+        self.on(date).next().is_some()
+    }
+}
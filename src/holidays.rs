@@ -0,0 +1,324 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Public holidays
+//!
+//! Computes the public holidays of a country or region from rules bundled in
+//! the binary (`data/holidays.tsv`): fixed dates, dates relative to Easter,
+//! nth (or last) weekday of a month, and first weekday on or after / last on
+//! or before a date. A subdivision (`DE-BY`) has the holidays of its country
+//! plus its own.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::sync::LazyLock;
+
+static RULES: LazyLock<Vec<HolidayRule>> =
+    LazyLock::new(|| parse_rules(include_str!("../data/holidays.tsv")));
+
+/// A public holiday on a given date
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    /// Name in the country's language
+    pub name: String,
+}
+
+/// When a holiday falls in a given year
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateRule {
+    /// `MM-DD`
+    Fixed { month: u32, day: u32 },
+    /// `easter±N`: days from Easter Sunday
+    Easter(i64),
+    /// `MM/nDay`: nth weekday of the month, counted from the end when negative
+    NthWeekday {
+        month: u32,
+        nth: i32,
+        weekday: Weekday,
+    },
+    /// `MM-DD>Day`: first `weekday` on or after the date
+    OnOrAfter {
+        month: u32,
+        day: u32,
+        weekday: Weekday,
+    },
+    /// `MM-DD<Day`: last `weekday` on or before the date
+    OnOrBefore {
+        month: u32,
+        day: u32,
+        weekday: Weekday,
+    },
+}
+
+/// Dataset entry
+#[derive(Debug, Clone)]
+struct HolidayRule {
+    /// Uppercase region codes the rule applies to
+    regions: Vec<String>,
+    date: DateRule,
+    name: String,
+    /// First year the holiday was observed
+    since: Option<i32>,
+}
+
+/// Parse `MM-DD`
+fn parse_month_day(text: &str) -> Option<(u32, u32)> {
+    let (month, day) = text.split_once('-')?;
+    Some((month.parse().ok()?, day.parse().ok()?))
+}
+
+impl DateRule {
+    fn parse(text: &str) -> Option<Self> {
+        if let Some(offset) = text.strip_prefix("easter") {
+            let offset = match offset {
+                "" => 0,
+                _ => offset.strip_prefix('+').unwrap_or(offset).parse().ok()?,
+            };
+            return Some(DateRule::Easter(offset));
+        }
+        if let Some((month, nth)) = text.split_once('/') {
+            let split = nth.find(|c: char| c.is_ascii_alphabetic())?;
+            let (nth, weekday) = nth.split_at(split);
+            return Some(DateRule::NthWeekday {
+                month: month.parse().ok()?,
+                nth: nth.parse().ok().filter(|nth| *nth != 0)?,
+                weekday: weekday.parse().ok()?,
+            });
+        }
+        if let Some((date, weekday)) = text.split_once('>') {
+            let (month, day) = parse_month_day(date)?;
+            let weekday = weekday.parse().ok()?;
+            return Some(DateRule::OnOrAfter {
+                month,
+                day,
+                weekday,
+            });
+        }
+        if let Some((date, weekday)) = text.split_once('<') {
+            let (month, day) = parse_month_day(date)?;
+            let weekday = weekday.parse().ok()?;
+            return Some(DateRule::OnOrBefore {
+                month,
+                day,
+                weekday,
+            });
+        }
+        let (month, day) = parse_month_day(text)?;
+        Some(DateRule::Fixed { month, day })
+    }
+
+    /// The date in `year` (`None` if the rule doesn't exist that year, like
+    /// a fifth Monday)
+    fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            DateRule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            DateRule::Easter(offset) => {
+                easter(year)?.checked_add_signed(chrono::TimeDelta::days(offset))
+            }
+            DateRule::NthWeekday {
+                month,
+                nth,
+                weekday,
+            } if nth > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, u8::try_from(nth).ok()?)
+            }
+            DateRule::NthWeekday {
+                month,
+                nth,
+                weekday,
+            } => {
+                let last = NaiveDate::from_ymd_opt(year, month, 1)?
+                    .checked_add_months(chrono::Months::new(1))?
+                    .pred_opt()?;
+                let back = (last.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                let weeks = u64::from(nth.unsigned_abs() - 1);
+                let date = last.checked_sub_days(Days::new(u64::from(back) + weeks * 7))?;
+                (date.month() == month).then_some(date)
+            }
+            DateRule::OnOrAfter {
+                month,
+                day,
+                weekday,
+            } => {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - date.weekday().num_days_from_monday())
+                    % 7;
+                date.checked_add_days(Days::new(u64::from(ahead)))
+            }
+            DateRule::OnOrBefore {
+                month,
+                day,
+                weekday,
+            } => {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                let back = (date.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                date.checked_sub_days(Days::new(u64::from(back)))
+            }
+        }
+    }
+}
+
+/// Parse the tab-separated rules, skipping comments and malformed lines
+fn parse_rules(data: &str) -> Vec<HolidayRule> {
+    data.lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let regions = fields.next()?;
+            let date = DateRule::parse(fields.next()?)?;
+            let name = fields.next()?;
+            let since = match fields.next() {
+                Some(year) => Some(year.trim().parse().ok()?),
+                None => None,
+            };
+            Some(HolidayRule {
+                regions: regions
+                    .split(',')
+                    .map(|region| region.trim().to_ascii_uppercase())
+                    .collect(),
+                date,
+                name: name.to_string(),
+                since,
+            })
+        })
+        .collect()
+}
+
+/// Easter Sunday of `year` in the Gregorian calendar (anonymous Gregorian
+/// algorithm, a.k.a. Meeus/Jones/Butcher)
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Check if a rule for `rule_region` applies in `region`: the region itself,
+/// or the country of a subdivision
+fn applies(rule_region: &str, region: &str) -> bool {
+    rule_region == region
+        || region
+            .split_once('-')
+            .is_some_and(|(country, _)| country == rule_region)
+}
+
+/// Holidays of `region` (ISO 3166 country or subdivision code) on the dates
+/// `first..=last`, in date order
+///
+/// Regions without rules have no holidays.
+pub fn between(region: &str, first: NaiveDate, last: NaiveDate) -> Vec<Holiday> {
+    let region = region.trim().to_ascii_uppercase();
+    if region.is_empty() {
+        return Vec::new();
+    }
+
+    let mut holidays: Vec<Holiday> = RULES
+        .iter()
+        .filter(|rule| rule.regions.iter().any(|r| applies(r, &region)))
+        .flat_map(|rule| {
+            (first.year()..=last.year())
+                .filter(|year| rule.since.is_none_or(|since| *year >= since))
+                .filter_map(|year| rule.date.date(year))
+                .filter(|date| (first..=last).contains(date))
+                .map(|date| Holiday {
+                    date,
+                    name: rule.name.clone(),
+                })
+        })
+        .collect();
+    holidays.sort_by_key(|holiday| holiday.date);
+    holidays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn names_on(region: &str, on: NaiveDate) -> Vec<String> {
+        between(region, on, on)
+            .into_iter()
+            .map(|holiday| holiday.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter(2000), Some(date(2000, 4, 23)));
+        assert_eq!(easter(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn test_dataset_parses() {
+        let data = include_str!("../data/holidays.tsv");
+        let lines = data
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+            .count();
+        assert_eq!(
+            parse_rules(data).len(),
+            lines,
+            "malformed rule in holidays.tsv"
+        );
+    }
+
+    #[test]
+    fn test_weekday_rules() {
+        assert_eq!(names_on("US", date(2025, 11, 27)), vec!["Thanksgiving Day"]);
+        assert_eq!(names_on("US", date(2025, 5, 26)), vec!["Memorial Day"]);
+        assert_eq!(names_on("CA", date(2025, 5, 19)), vec!["Victoria Day"]);
+        assert_eq!(names_on("SE", date(2025, 6, 21)), vec!["Midsommardagen"]);
+        assert_eq!(
+            names_on("DE-SN", date(2025, 11, 19)),
+            vec!["Buß- und Bettag"]
+        );
+    }
+
+    #[test]
+    fn test_regions() {
+        // Corpus Christi 2025: Bavaria, not Berlin
+        let corpus_christi = date(2025, 6, 19);
+        assert_eq!(names_on("DE-BY", corpus_christi), vec!["Fronleichnam"]);
+        assert!(names_on("de-be", corpus_christi).is_empty());
+        // Subdivisions inherit the national holidays
+        assert_eq!(
+            names_on("DE-BY", date(2025, 10, 3)),
+            vec!["Tag der Deutschen Einheit"]
+        );
+        // Observed since 2021 only
+        assert!(names_on("US", date(2020, 6, 19)).is_empty());
+        assert!(between("XX", date(2025, 1, 1), date(2025, 12, 31)).is_empty());
+
+        let brazil = between("BR", date(2025, 1, 1), date(2025, 12, 31));
+        assert_eq!(brazil.len(), 13);
+        assert_eq!(
+            brazil[1],
+            Holiday {
+                date: date(2025, 3, 3),
+                name: "Carnaval".to_string()
+            }
+        );
+    }
+}
@@ -15,8 +15,71 @@ pub mod navigation {
     pub const NEXT: &str = "go-next-symbolic";
 }
 
-/// Generic UI icons
-pub mod ui {
-    /// Starred/featured item indicator icon
-    pub const STARRED: &str = "starred-symbolic";
+/// Calendar view icons
+pub mod calendar {
+    /// Switch to the agenda of upcoming events icon
+    pub const AGENDA: &str = "view-list-symbolic";
+
+    /// Back to the month grid icon
+    pub const MONTH: &str = "x-office-calendar-symbolic";
+}
+
+/// Timer control icons
+pub mod timer {
+    /// Start or resume a countdown icon
+    pub const START: &str = "media-playback-start-symbolic";
+
+    /// Pause a countdown icon
+    pub const PAUSE: &str = "media-playback-pause-symbolic";
+
+    /// Restart the current phase icon
+    pub const RESTART: &str = "view-refresh-symbolic";
+
+    /// Skip to the next phase icon
+    pub const SKIP: &str = "media-skip-forward-symbolic";
+
+    /// Copy laps to the clipboard icon
+    pub const COPY: &str = "edit-copy-symbolic";
+
+    /// Delete an alarm or timer icon
+    pub const DELETE: &str = "edit-delete-symbolic";
+}
+
+/// Weather condition icons
+pub mod weather {
+    /// Clear sky (day) icon
+    pub const CLEAR: &str = "weather-clear-symbolic";
+
+    /// Clear sky (night) icon
+    pub const CLEAR_NIGHT: &str = "weather-clear-night-symbolic";
+
+    /// Partly cloudy (day) icon
+    pub const FEW_CLOUDS: &str = "weather-few-clouds-symbolic";
+
+    /// Partly cloudy (night) icon
+    pub const FEW_CLOUDS_NIGHT: &str = "weather-few-clouds-night-symbolic";
+
+    /// Overcast sky icon
+    pub const OVERCAST: &str = "weather-overcast-symbolic";
+
+    /// Fog icon
+    pub const FOG: &str = "weather-fog-symbolic";
+
+    /// Drizzle and rain showers icon
+    pub const SHOWERS_SCATTERED: &str = "weather-showers-scattered-symbolic";
+
+    /// Steady rain icon
+    pub const SHOWERS: &str = "weather-showers-symbolic";
+
+    /// Snow icon
+    pub const SNOW: &str = "weather-snow-symbolic";
+
+    /// Thunderstorm icon
+    pub const STORM: &str = "weather-storm-symbolic";
+
+    /// Outdated (cached) report badge icon
+    pub const STALE: &str = "network-offline-symbolic";
+
+    /// Severe weather alert banner and notification icon
+    pub const ALERT: &str = "dialog-warning-symbolic";
 }
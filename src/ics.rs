@@ -0,0 +1,1111 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! iCalendar parser
+//!
+//! Reads the VEVENTs of RFC 5545 files (`.ics`) and expands their recurrences
+//! (RRULE, RDATE, EXDATE and RECURRENCE-ID overrides) into occurrences. Only
+//! what the calendar shows is kept: summary, location, description,
+//! attendees, start and end. Alarms and VTIMEZONE definitions are ignored;
+//! TZID must name an IANA zone, which is what common calendar apps write.
+//!
+//! Supported RRULE parts: FREQ (DAILY, WEEKLY, MONTHLY, YEARLY), INTERVAL,
+//! COUNT, UNTIL, BYDAY, BYMONTHDAY, BYMONTH, BYSETPOS and WKST. Other parts
+//! (BYYEARDAY, BYWEEKNO, BYHOUR, ...) are ignored.
+
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    TimeZone, Weekday,
+};
+use chrono_tz::Tz;
+
+/// Most recurrence periods walked for one event (e.g. 27 years of a daily
+/// event with a COUNT)
+const MAX_PERIODS: u64 = 10_000;
+
+// ============================================================================
+// Event Model
+// ============================================================================
+
+/// Start of an event (or of one of its occurrences), as written in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    /// All-day (`VALUE=DATE`)
+    Date(NaiveDate),
+    /// Wall-clock time in a zone; `None` is floating (the viewer's local time)
+    DateTime(NaiveDateTime, Option<Tz>),
+}
+
+impl EventTime {
+    fn date(self) -> NaiveDate {
+        match self {
+            EventTime::Date(date) => date,
+            EventTime::DateTime(time, _) => time.date(),
+        }
+    }
+
+    /// Same time of day (and zone) on another date
+    fn with_date(self, date: NaiveDate) -> Self {
+        match self {
+            EventTime::Date(_) => EventTime::Date(date),
+            EventTime::DateTime(time, zone) => {
+                EventTime::DateTime(date.and_time(time.time()), zone)
+            }
+        }
+    }
+
+    /// Sortable point in time: UTC for zoned times, wall clock otherwise
+    fn key(self) -> NaiveDateTime {
+        match self {
+            EventTime::Date(date) => date.and_time(NaiveTime::MIN),
+            EventTime::DateTime(time, None) => time,
+            EventTime::DateTime(time, Some(zone)) => {
+                local_instant(&zone, time).map_or(time, |instant| instant.naive_utc())
+            }
+        }
+    }
+
+    /// Check if both name the same occurrence (a date matches any time on it)
+    fn matches(self, other: EventTime) -> bool {
+        match (self, other) {
+            (EventTime::DateTime(..), EventTime::DateTime(..)) => self.key() == other.key(),
+            _ => self.date() == other.date(),
+        }
+    }
+
+    /// Instant in `tz` (floating times are read as wall-clock time of `tz`)
+    fn resolve<Z: TimeZone>(self, tz: &Z) -> Option<DateTime<FixedOffset>> {
+        match self {
+            EventTime::Date(date) => local_instant(tz, date.and_time(NaiveTime::MIN)),
+            EventTime::DateTime(time, None) => local_instant(tz, time),
+            EventTime::DateTime(time, Some(zone)) => {
+                local_instant(&zone, time).map(|instant| instant.with_timezone(tz).fixed_offset())
+            }
+        }
+    }
+}
+
+/// Instant of a wall-clock time; times skipped by a DST change move forward
+fn local_instant<Z: TimeZone>(tz: &Z, time: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    tz.from_local_datetime(&time)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(time + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .map(|instant| instant.fixed_offset())
+}
+
+/// A calendar event, possibly recurring
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub uid: String,
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    /// Attendee names (or addresses when unnamed)
+    pub attendees: Vec<String>,
+    pub start: EventTime,
+    /// Length of each occurrence
+    pub duration: TimeDelta,
+    rule: Option<Rule>,
+    /// Extra occurrences (RDATE)
+    rdates: Vec<EventTime>,
+    /// Removed occurrences (EXDATE, and those replaced by overrides)
+    exdates: Vec<EventTime>,
+    /// Occurrence of a recurring event this one replaces (RECURRENCE-ID)
+    recurrence_id: Option<EventTime>,
+}
+
+/// One occurrence of an event, in the display timezone
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub attendees: Vec<String>,
+    pub time: OccurrenceTime,
+}
+
+/// When an occurrence takes place
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OccurrenceTime {
+    /// Whole days, `last` included
+    AllDay { first: NaiveDate, last: NaiveDate },
+    Timed {
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+    },
+}
+
+impl Occurrence {
+    /// First day the occurrence is on
+    pub fn first_day(&self) -> NaiveDate {
+        match self.time {
+            OccurrenceTime::AllDay { first, .. } => first,
+            OccurrenceTime::Timed { start, .. } => start.date_naive(),
+        }
+    }
+
+    /// Last day the occurrence is on (an event ending at midnight is not on
+    /// the next day)
+    pub fn last_day(&self) -> NaiveDate {
+        match self.time {
+            OccurrenceTime::AllDay { last, .. } => last,
+            OccurrenceTime::Timed { start, end } if end > start => {
+                (end - TimeDelta::nanoseconds(1)).date_naive()
+            }
+            OccurrenceTime::Timed { start, .. } => start.date_naive(),
+        }
+    }
+
+    /// Check if the occurrence takes place (at least partly) on `date`
+    pub fn is_on(&self, date: NaiveDate) -> bool {
+        (self.first_day()..=self.last_day()).contains(&date)
+    }
+
+    /// Sort key: all-day occurrences first, then by start time
+    pub fn sort_key(&self) -> (NaiveDate, Option<DateTime<FixedOffset>>) {
+        match self.time {
+            OccurrenceTime::AllDay { first, .. } => (first, None),
+            OccurrenceTime::Timed { start, .. } => (start.date_naive(), Some(start)),
+        }
+    }
+}
+
+impl Event {
+    /// Occurrences on the dates `first..=last`, in `tz`
+    pub fn occurrences<Z: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Z,
+    ) -> Vec<Occurrence> {
+        // Look a bit further in the event's own zone: occurrences move by up
+        // to a day in `tz`, and long ones start before the range
+        let margin = Days::new(self.duration.num_days().unsigned_abs() + 1);
+        let from = first.checked_sub_days(margin).unwrap_or(first);
+        let through = last.checked_add_days(Days::new(1)).unwrap_or(last);
+
+        let mut starts: Vec<EventTime> = match &self.rule {
+            Some(rule) => rule
+                .dates(self.start, from, through)
+                .into_iter()
+                .map(|date| self.start.with_date(date))
+                .collect(),
+            None => vec![self.start],
+        };
+        starts.extend(self.rdates.iter().copied());
+        starts.retain(|start| !self.exdates.iter().any(|exdate| exdate.matches(*start)));
+
+        let mut occurrences: Vec<Occurrence> = starts
+            .into_iter()
+            .filter_map(|start| self.occurrence(start, tz))
+            .filter(|occurrence| occurrence.last_day() >= first && occurrence.first_day() <= last)
+            .collect();
+        occurrences.sort_by_key(Occurrence::sort_key);
+        occurrences.dedup();
+        occurrences
+    }
+
+    fn occurrence<Z: TimeZone>(&self, start: EventTime, tz: &Z) -> Option<Occurrence> {
+        let time = match start {
+            EventTime::Date(first) => {
+                let days = self.duration.num_days().max(1) as u64;
+                OccurrenceTime::AllDay {
+                    first,
+                    last: first.checked_add_days(Days::new(days - 1))?,
+                }
+            }
+            EventTime::DateTime(..) => {
+                let start = start.resolve(tz)?;
+                OccurrenceTime::Timed {
+                    start,
+                    end: start.checked_add_signed(self.duration)?,
+                }
+            }
+        };
+        Some(Occurrence {
+            summary: self.summary.clone(),
+            location: self.location.clone(),
+            description: self.description.clone(),
+            attendees: self.attendees.clone(),
+            time,
+        })
+    }
+}
+
+// ============================================================================
+// Recurrence Rules
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Parsed RRULE
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<EventTime>,
+    /// Weekdays, with an optional ordinal ("2MO" = second Monday, "-1FR" = last Friday)
+    by_day: Vec<(Option<i32>, Weekday)>,
+    /// Days of the month, negative from the end
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    /// Positions within each period's set, negative from the end
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+}
+
+impl Rule {
+    /// Parse an RRULE value (`FREQ=WEEKLY;BYDAY=MO,WE`), `None` if unsupported
+    fn parse(value: &str) -> Option<Self> {
+        let mut rule = Rule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        let mut frequency = None;
+        for part in value.split(';') {
+            let (name, value) = part.split_once('=')?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        // Sub-daily events would flood the calendar
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|n| *n > 0)?,
+                "COUNT" => rule.count = value.parse().ok(),
+                "UNTIL" => rule.until = parse_time(value, None),
+                "BYDAY" => rule.by_day = value.split(',').filter_map(parse_by_day).collect(),
+                "BYMONTHDAY" => rule.by_month_day = parse_list(value),
+                "BYMONTH" => rule.by_month = parse_list(value),
+                "BYSETPOS" => rule.by_set_pos = parse_list(value),
+                "WKST" => rule.week_start = parse_weekday(value)?,
+                _ => tracing::debug!("[Calendar] Ignoring RRULE part {}", part),
+            }
+        }
+        rule.frequency = frequency?;
+        Some(rule)
+    }
+
+    /// Dates of the occurrences starting `from..=through`, DTSTART included
+    fn dates(&self, start: EventTime, from: NaiveDate, through: NaiveDate) -> Vec<NaiveDate> {
+        let dtstart = start.date();
+        let until = self.until.map(|until| match until {
+            // A date includes its whole day
+            EventTime::Date(date) => date.and_time(NaiveTime::MIN) + TimeDelta::days(1),
+            time => time.key() + TimeDelta::seconds(1),
+        });
+        let in_rule =
+            |date: NaiveDate| until.is_none_or(|until| start.with_date(date).key() < until);
+
+        // Without a COUNT, periods before the range need not be walked
+        let skip = match self.count {
+            Some(_) => 0,
+            None => self.periods_between(dtstart, from).saturating_sub(1),
+        };
+
+        let mut dates = Vec::new();
+        let mut count = 0;
+        let mut take = |date: NaiveDate, dates: &mut Vec<NaiveDate>| {
+            if self.count.is_some_and(|max| count >= max) || !in_rule(date) || date > through {
+                return false;
+            }
+            count += 1;
+            if date >= from {
+                dates.push(date);
+            }
+            true
+        };
+
+        // DTSTART is the first occurrence, even when it doesn't match the rule
+        if skip == 0 && !take(dtstart, &mut dates) {
+            return dates;
+        }
+        for period in skip..skip + MAX_PERIODS {
+            let Some(offset) = period
+                .checked_mul(u64::from(self.interval))
+                .and_then(|offset| u32::try_from(offset).ok())
+            else {
+                break;
+            };
+            for date in self.period_dates(dtstart, offset) {
+                if date > dtstart && !take(date, &mut dates) {
+                    return dates;
+                }
+            }
+        }
+        dates
+    }
+
+    /// Whole periods (of the rule's frequency and interval) from `dtstart` to `date`
+    fn periods_between(&self, dtstart: NaiveDate, date: NaiveDate) -> u64 {
+        let days = (date - dtstart).num_days().max(0) as u64;
+        let months = ((date.year() - dtstart.year()) * 12 + date.month() as i32
+            - dtstart.month() as i32)
+            .max(0) as u64;
+        let periods = match self.frequency {
+            Frequency::Daily => days,
+            Frequency::Weekly => days / 7,
+            Frequency::Monthly => months,
+            Frequency::Yearly => months / 12,
+        };
+        periods / u64::from(self.interval)
+    }
+
+    /// Candidate dates of the period `offset` periods after the one of `dtstart`
+    fn period_dates(&self, dtstart: NaiveDate, offset: u32) -> Vec<NaiveDate> {
+        let mut dates = match self.frequency {
+            Frequency::Daily => dtstart
+                .checked_add_days(Days::new(offset.into()))
+                .filter(|date| self.matches_filters(*date))
+                .into_iter()
+                .collect(),
+            Frequency::Weekly => self.week_dates(dtstart, offset),
+            Frequency::Monthly => dtstart
+                .with_day(1)
+                .and_then(|first| first.checked_add_months(Months::new(offset)))
+                .map(|month| self.month_dates(dtstart, month))
+                .unwrap_or_default(),
+            Frequency::Yearly => i32::try_from(offset)
+                .ok()
+                .and_then(|offset| dtstart.year().checked_add(offset))
+                .map(|year| self.year_dates(dtstart, year))
+                .unwrap_or_default(),
+        };
+        dates.sort();
+        dates.dedup();
+        self.select_positions(dates)
+    }
+
+    /// Daily occurrences are limited (not expanded) by the BY* parts
+    fn matches_filters(&self, date: NaiveDate) -> bool {
+        (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+            && (self.by_month_day.is_empty()
+                || month_days(date, &self.by_month_day).contains(&date))
+            && (self.by_day.is_empty() || self.by_day.iter().any(|(_, day)| *day == date.weekday()))
+    }
+
+    fn week_dates(&self, dtstart: NaiveDate, offset: u32) -> Vec<NaiveDate> {
+        let since_week_start = dtstart.weekday().days_since(self.week_start);
+        let Some(week) = dtstart
+            .checked_sub_days(Days::new(since_week_start.into()))
+            .and_then(|week| week.checked_add_days(Days::new(u64::from(offset) * 7)))
+        else {
+            return Vec::new();
+        };
+        let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+            vec![dtstart.weekday()]
+        } else {
+            self.by_day.iter().map(|(_, day)| *day).collect()
+        };
+        weekdays
+            .into_iter()
+            .filter_map(|day| {
+                week.checked_add_days(Days::new(day.days_since(self.week_start).into()))
+            })
+            .filter(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()))
+            .collect()
+    }
+
+    /// Dates in the month of `month` (any date of it)
+    fn month_dates(&self, dtstart: NaiveDate, month: NaiveDate) -> Vec<NaiveDate> {
+        if !self.by_month.is_empty() && !self.by_month.contains(&month.month()) {
+            return Vec::new();
+        }
+        let by_month_day = month_days(month, &self.by_month_day);
+        let by_day = weekdays_in(month_first(month), month_last(month), &self.by_day);
+        match (self.by_month_day.is_empty(), self.by_day.is_empty()) {
+            (true, true) => month.with_day(dtstart.day()).into_iter().collect(),
+            (false, true) => by_month_day,
+            (true, false) => by_day,
+            (false, false) => by_day
+                .into_iter()
+                .filter(|date| by_month_day.contains(date))
+                .collect(),
+        }
+    }
+
+    fn year_dates(&self, dtstart: NaiveDate, year: i32) -> Vec<NaiveDate> {
+        let month_of = |month: u32| NaiveDate::from_ymd_opt(year, month, 1);
+        if self.by_month.is_empty() && self.by_month_day.is_empty() && !self.by_day.is_empty() {
+            // "20MO" is the 20th Monday of the year
+            let (Some(first), Some(last)) = (
+                NaiveDate::from_ymd_opt(year, 1, 1),
+                NaiveDate::from_ymd_opt(year, 12, 31),
+            ) else {
+                return Vec::new();
+            };
+            return weekdays_in(first, last, &self.by_day);
+        }
+        let months: Vec<u32> = match (self.by_month.is_empty(), self.by_month_day.is_empty()) {
+            (false, _) => self.by_month.clone(),
+            (true, false) => (1..=12).collect(),
+            (true, true) => vec![dtstart.month()],
+        };
+        months
+            .into_iter()
+            .filter_map(month_of)
+            .flat_map(|month| {
+                if self.by_month_day.is_empty() && self.by_day.is_empty() {
+                    // Skips February in non-leap years for a 29th
+                    month.with_day(dtstart.day()).into_iter().collect()
+                } else {
+                    self.month_dates(dtstart, month)
+                }
+            })
+            .collect()
+    }
+
+    /// Apply BYSETPOS to the sorted dates of one period
+    fn select_positions(&self, dates: Vec<NaiveDate>) -> Vec<NaiveDate> {
+        if self.by_set_pos.is_empty() {
+            return dates;
+        }
+        let mut selected: Vec<NaiveDate> = self
+            .by_set_pos
+            .iter()
+            .filter_map(|pos| position(&dates, *pos))
+            .collect();
+        selected.sort();
+        selected.dedup();
+        selected
+    }
+}
+
+/// Element at a 1-based position, negative from the end
+fn position<T: Copy>(items: &[T], pos: i32) -> Option<T> {
+    let index = match pos {
+        0 => return None,
+        pos if pos > 0 => pos as usize - 1,
+        pos => items.len().checked_sub(pos.unsigned_abs() as usize)?,
+    };
+    items.get(index).copied()
+}
+
+fn month_first(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn month_last(date: NaiveDate) -> NaiveDate {
+    month_first(date)
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+/// Dates of the month of `date` named by BYMONTHDAY values
+fn month_days(date: NaiveDate, days: &[i32]) -> Vec<NaiveDate> {
+    let length = month_last(date).day() as i32;
+    days.iter()
+        .filter_map(|day| match *day {
+            day if day > 0 => date.with_day(day as u32),
+            day if day < 0 && -day <= length => date.with_day((length + day + 1) as u32),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Dates `first..=last` matching BYDAY values, ordinals counted in that span
+fn weekdays_in(
+    first: NaiveDate,
+    last: NaiveDate,
+    by_day: &[(Option<i32>, Weekday)],
+) -> Vec<NaiveDate> {
+    by_day
+        .iter()
+        .flat_map(|(ordinal, weekday)| {
+            let days: Vec<NaiveDate> = first
+                .iter_days()
+                .take_while(|date| *date <= last)
+                .filter(|date| date.weekday() == *weekday)
+                .collect();
+            match ordinal {
+                Some(ordinal) => position(&days, *ordinal).into_iter().collect(),
+                None => days,
+            }
+        })
+        .collect()
+}
+
+/// Comma-separated numbers, skipping invalid ones
+fn parse_list<T: std::str::FromStr>(value: &str) -> Vec<T> {
+    value
+        .split(',')
+        .filter_map(|item| item.trim().parse().ok())
+        .collect()
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    Some(match value.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// BYDAY value: "MO", "2MO", "-1FR"
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim();
+    let split = value.len().checked_sub(2)?;
+    let weekday = parse_weekday(value.get(split..)?)?;
+    let ordinal = match value.get(..split)? {
+        "" => None,
+        ordinal => Some(ordinal.trim_start_matches('+').parse().ok()?),
+    };
+    Some((ordinal, weekday))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Property of a content line: name, parameters and raw value
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl<'a> Property<'a> {
+    /// Split `NAME;PARAM=value;...:VALUE` (colons and semicolons inside
+    /// quoted parameter values don't count)
+    fn parse(line: &'a str) -> Option<Self> {
+        let mut quoted = false;
+        let mut separators = Vec::new();
+        let mut colon = None;
+        for (index, c) in line.char_indices() {
+            match c {
+                '"' => quoted = !quoted,
+                ';' if !quoted => separators.push(index),
+                ':' if !quoted => {
+                    colon = Some(index);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let colon = colon?;
+        let mut bounds = separators;
+        bounds.push(colon);
+        let name = line[..bounds[0]].to_ascii_uppercase();
+        let params = bounds
+            .windows(2)
+            .filter_map(|pair| {
+                let (key, value) = line[pair[0] + 1..pair[1]].split_once('=')?;
+                Some((key.to_ascii_uppercase(), value.trim_matches('"')))
+            })
+            .collect();
+        Some(Self {
+            name,
+            params,
+            value: &line[colon + 1..],
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&'a str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// Date or date-time value(s), comma-separated
+    fn times(&self) -> Vec<EventTime> {
+        let zone = self.param("TZID").and_then(parse_tzid);
+        let date_only = self
+            .param("VALUE")
+            .is_some_and(|value| value.eq_ignore_ascii_case("DATE"));
+        self.value
+            .split(',')
+            .filter_map(|value| {
+                if date_only {
+                    parse_date(value).map(EventTime::Date)
+                } else {
+                    parse_time(value, zone)
+                }
+            })
+            .collect()
+    }
+
+    fn time(&self) -> Option<EventTime> {
+        self.times().into_iter().next()
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y%m%d").ok()
+}
+
+/// DATE or DATE-TIME value; a trailing `Z` means UTC, otherwise `zone`
+fn parse_time(value: &str, zone: Option<Tz>) -> Option<EventTime> {
+    let value = value.trim();
+    if value.len() == 8 {
+        return parse_date(value).map(EventTime::Date);
+    }
+    let (value, zone) = match value.strip_suffix('Z') {
+        Some(utc) => (utc, Some(Tz::UTC)),
+        None => (value, zone),
+    };
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some(EventTime::DateTime(time, zone))
+}
+
+/// IANA zone of a TZID, also when prefixed ("/mozilla.org/20050126_1/Europe/Berlin")
+fn parse_tzid(tzid: &str) -> Option<Tz> {
+    let zone = std::iter::once(tzid)
+        .chain(tzid.match_indices('/').map(|(index, _)| &tzid[index + 1..]))
+        .find_map(|candidate| candidate.parse::<Tz>().ok());
+    if zone.is_none() {
+        tracing::debug!("[Calendar] Unknown TZID {}, using local time", tzid);
+    }
+    zone
+}
+
+/// DURATION value: "PT1H30M", "P1D", "P2W", "-PT15M"
+fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                let part = match unit {
+                    'W' => TimeDelta::try_weeks(n),
+                    'D' => TimeDelta::try_days(n),
+                    'H' => TimeDelta::try_hours(n),
+                    'M' => TimeDelta::try_minutes(n),
+                    'S' => TimeDelta::try_seconds(n),
+                    _ => None,
+                }?;
+                total = total.checked_add(&part)?;
+            }
+        }
+    }
+    total.checked_mul(sign)
+}
+
+/// Length between a start and an end written in (possibly) different zones
+fn duration_between(start: EventTime, end: EventTime) -> TimeDelta {
+    match (start, end) {
+        (EventTime::Date(start), EventTime::Date(end)) => end - start,
+        (EventTime::DateTime(_, Some(_)), EventTime::DateTime(_, Some(_))) => {
+            end.key() - start.key()
+        }
+        (EventTime::DateTime(start, _), EventTime::DateTime(end, _)) => end - start,
+        _ => end.date() - start.date(),
+    }
+    .max(TimeDelta::zero())
+}
+
+/// Unescape a TEXT value
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push('\n'),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Name of an ATTENDEE: its CN parameter, else the address without `mailto:`
+fn attendee(property: &Property) -> Option<String> {
+    let name = match property.param("CN").map(str::trim) {
+        Some(name) if !name.is_empty() => name,
+        _ => {
+            let address = property.value.trim();
+            address
+                .get(..7)
+                .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+                .map_or(address, |_| &address[7..])
+        }
+    };
+    Some(name.to_string()).filter(|name| !name.is_empty())
+}
+
+/// VEVENT being read
+#[derive(Default)]
+struct EventBuilder {
+    uid: String,
+    summary: String,
+    location: Option<String>,
+    description: Option<String>,
+    attendees: Vec<String>,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    duration: Option<TimeDelta>,
+    rule: Option<Rule>,
+    rdates: Vec<EventTime>,
+    exdates: Vec<EventTime>,
+    recurrence_id: Option<EventTime>,
+    cancelled: bool,
+}
+
+impl EventBuilder {
+    fn set(&mut self, property: &Property) {
+        match property.name.as_str() {
+            "UID" => self.uid = property.value.to_string(),
+            "SUMMARY" => self.summary = unescape(property.value),
+            "LOCATION" => {
+                self.location = Some(unescape(property.value)).filter(|text| !text.is_empty())
+            }
+            "DESCRIPTION" => {
+                self.description =
+                    Some(unescape(property.value)).filter(|text| !text.trim().is_empty())
+            }
+            "ATTENDEE" => self.attendees.extend(attendee(property)),
+            "DTSTART" => self.start = property.time(),
+            "DTEND" => self.end = property.time(),
+            "DURATION" => self.duration = parse_duration(property.value),
+            "RRULE" => {
+                self.rule = Rule::parse(property.value);
+                if self.rule.is_none() {
+                    tracing::debug!("[Calendar] Unsupported RRULE {}", property.value);
+                }
+            }
+            "RDATE" => self.rdates.extend(property.times()),
+            "EXDATE" => self.exdates.extend(property.times()),
+            "RECURRENCE-ID" => self.recurrence_id = property.time(),
+            "STATUS" => self.cancelled = property.value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    fn build(self) -> Option<(Event, bool)> {
+        let start = self.start?;
+        let duration = match (self.end, self.duration) {
+            (Some(end), _) => duration_between(start, end),
+            (None, Some(duration)) => duration.max(TimeDelta::zero()),
+            // RFC 5545: one day for dates, an instant for date-times
+            (None, None) => match start {
+                EventTime::Date(_) => TimeDelta::days(1),
+                EventTime::DateTime(..) => TimeDelta::zero(),
+            },
+        };
+        let event = Event {
+            uid: self.uid,
+            summary: self.summary,
+            location: self.location,
+            description: self.description,
+            attendees: self.attendees,
+            start,
+            duration,
+            rule: self.rule,
+            rdates: self.rdates,
+            exdates: self.exdates,
+            recurrence_id: self.recurrence_id,
+        };
+        Some((event, self.cancelled))
+    }
+}
+
+/// Events of an iCalendar document (malformed events are skipped)
+///
+/// Overrides of single occurrences (RECURRENCE-ID) replace the occurrence of
+/// their recurring event; cancelled ones just remove it.
+pub fn parse_calendar(text: &str) -> Vec<Event> {
+    // Unfold: a line starting with a space or tab continues the previous one
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut parsed = Vec::new();
+    let mut event: Option<EventBuilder> = None;
+    // Components nested in the VEVENT (VALARM)
+    let mut nested = 0;
+    for line in &lines {
+        let Some(property) = Property::parse(line) else {
+            continue;
+        };
+        let is_vevent = property.value.trim().eq_ignore_ascii_case("VEVENT");
+        match (property.name.as_str(), event.as_mut()) {
+            ("BEGIN", None) if is_vevent => event = Some(EventBuilder::default()),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if is_vevent => {
+                if let Some(built) = event.take().and_then(EventBuilder::build) {
+                    parsed.push(built);
+                } else {
+                    tracing::debug!("[Calendar] Skipping VEVENT without DTSTART");
+                }
+            }
+            (_, Some(builder)) if nested == 0 => builder.set(&property),
+            _ => {}
+        }
+    }
+
+    // Overridden occurrences are removed from their recurring event
+    let overrides: Vec<(String, EventTime)> = parsed
+        .iter()
+        .filter_map(|(event, _)| Some((event.uid.clone(), event.recurrence_id?)))
+        .collect();
+    parsed
+        .into_iter()
+        .filter(|(_, cancelled)| !cancelled)
+        .map(|(mut event, _)| {
+            if event.recurrence_id.is_none() {
+                event.exdates.extend(
+                    overrides
+                        .iter()
+                        .filter(|(uid, _)| *uid == event.uid)
+                        .map(|(_, id)| *id),
+                );
+            }
+            event
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn calendar(events: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{events}END:VCALENDAR\r\n")
+    }
+
+    /// First days of the occurrences in January 2025 (UTC)
+    fn january(event: &Event) -> Vec<u32> {
+        event
+            .occurrences(date(2025, 1, 1), date(2025, 1, 31), &chrono::Utc)
+            .iter()
+            .map(|occurrence| occurrence.first_day().day())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let text = calendar(
+            "BEGIN:VEVENT\r\n\
+             UID:1@example.com\r\n\
+             SUMMARY:Team sync\\, weekly\r\n\
+             LOCATION:Room 4\r\n\
+             DESCRIPTION:A long description that is\r\n  folded over two lines\r\n\
+             ATTENDEE;CN=\"Ana, Lima\";ROLE=REQ-PARTICIPANT:mailto:ana@example.com\r\n\
+             ATTENDEE:MAILTO:bob@example.com\r\n\
+             DTSTART;TZID=Europe/Berlin:20250115T100000\r\n\
+             DTEND;TZID=Europe/Berlin:20250115T113000\r\n\
+             BEGIN:VALARM\r\n\
+             TRIGGER:-PT15M\r\n\
+             SUMMARY:Reminder\r\n\
+             END:VALARM\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:2@example.com\r\n\
+             SUMMARY:Conference\r\n\
+             DTSTART;VALUE=DATE:20250120\r\n\
+             DTEND;VALUE=DATE:20250123\r\n\
+             END:VEVENT\r\n",
+        );
+        let events = parse_calendar(&text);
+        assert_eq!(events.len(), 2);
+
+        let sync = &events[0];
+        assert_eq!(sync.summary, "Team sync, weekly");
+        assert_eq!(sync.location.as_deref(), Some("Room 4"));
+        assert_eq!(
+            sync.description.as_deref(),
+            Some("A long description that is folded over two lines")
+        );
+        assert_eq!(sync.attendees, vec!["Ana, Lima", "bob@example.com"]);
+        assert_eq!(sync.duration, TimeDelta::minutes(90));
+        let occurrences = sync.occurrences(date(2025, 1, 15), date(2025, 1, 15), &chrono::Utc);
+        let OccurrenceTime::Timed { start, .. } = occurrences[0].time else {
+            panic!("timed event: {occurrences:?}");
+        };
+        assert_eq!(start.hour(), 9, "10:00 CET is 09:00 UTC");
+
+        let conference = &events[1];
+        assert_eq!(january(conference), vec![20]);
+        let occurrence =
+            &conference.occurrences(date(2025, 1, 1), date(2025, 1, 31), &chrono::Utc)[0];
+        assert_eq!(occurrence.last_day(), date(2025, 1, 22));
+        assert!(occurrence.is_on(date(2025, 1, 21)));
+        assert!(!occurrence.is_on(date(2025, 1, 23)));
+    }
+
+    #[test]
+    fn test_weekly_rule_with_exceptions() {
+        let text = calendar(
+            "BEGIN:VEVENT\r\n\
+             UID:standup\r\n\
+             SUMMARY:Standup\r\n\
+             DTSTART:20250106T090000Z\r\n\
+             DURATION:PT15M\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,TH;UNTIL=20250123T235959Z\r\n\
+             EXDATE:20250109T090000Z\r\n\
+             RDATE:20250125T090000Z\r\n\
+             END:VEVENT\r\n",
+        );
+        let events = parse_calendar(&text);
+        assert_eq!(january(&events[0]), vec![6, 13, 16, 20, 23, 25]);
+    }
+
+    #[test]
+    fn test_monthly_and_yearly_rules() {
+        let event = |rule: &str, start: &str| {
+            let text = calendar(&format!(
+                "BEGIN:VEVENT\r\nUID:x\r\nDTSTART;VALUE=DATE:{start}\r\nRRULE:{rule}\r\nEND:VEVENT\r\n"
+            ));
+            parse_calendar(&text).remove(0)
+        };
+        let firsts = |event: &Event, first: NaiveDate, last: NaiveDate| -> Vec<NaiveDate> {
+            event
+                .occurrences(first, last, &chrono::Utc)
+                .iter()
+                .map(Occurrence::first_day)
+                .collect()
+        };
+
+        // Last Friday of the month, three times
+        let last_friday = event("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", "20250131");
+        assert_eq!(
+            firsts(&last_friday, date(2025, 1, 1), date(2025, 12, 31)),
+            vec![date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 28)]
+        );
+
+        // Last working day of the month
+        let payday = event("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1", "20250131");
+        assert_eq!(
+            firsts(&payday, date(2025, 5, 1), date(2025, 6, 30)),
+            vec![date(2025, 5, 30), date(2025, 6, 30)]
+        );
+
+        // Monthly on the 31st: months without one are skipped
+        let monthly = event("FREQ=MONTHLY", "20250131");
+        assert_eq!(
+            firsts(&monthly, date(2025, 1, 1), date(2025, 6, 30)),
+            vec![date(2025, 1, 31), date(2025, 3, 31), date(2025, 5, 31)]
+        );
+
+        // Every other week on Tuesdays and Thursdays, weeks starting on Sunday
+        let fortnightly = event("FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;WKST=SU", "20250107");
+        assert_eq!(
+            firsts(&fortnightly, date(2025, 3, 1), date(2025, 3, 14)),
+            vec![date(2025, 3, 4), date(2025, 3, 6)]
+        );
+
+        // Fourth Thursday of November, decades after DTSTART
+        let thanksgiving = event("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH", "19421126");
+        assert_eq!(
+            firsts(&thanksgiving, date(2025, 1, 1), date(2026, 12, 31)),
+            vec![date(2025, 11, 27), date(2026, 11, 26)]
+        );
+
+        let birthday = event("FREQ=YEARLY", "20000229");
+        assert_eq!(
+            firsts(&birthday, date(2025, 1, 1), date(2028, 12, 31)),
+            vec![date(2028, 2, 29)]
+        );
+
+        // Only DTSTART: the next period is past the last representable year
+        let rare = event("FREQ=YEARLY;INTERVAL=2147483000", "20250101");
+        assert_eq!(
+            firsts(&rare, date(2025, 1, 1), date(2025, 12, 31)),
+            vec![date(2025, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_overridden_and_cancelled_occurrences() {
+        let text = calendar(
+            "BEGIN:VEVENT\r\n\
+             UID:daily\r\n\
+             SUMMARY:Daily\r\n\
+             DTSTART;TZID=/mozilla.org/20050126_1/America/New_York:20250110T080000\r\n\
+             RRULE:FREQ=DAILY;COUNT=5\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:daily\r\n\
+             SUMMARY:Daily (moved)\r\n\
+             RECURRENCE-ID;TZID=America/New_York:20250111T080000\r\n\
+             DTSTART;TZID=America/New_York:20250111T150000\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:daily\r\n\
+             RECURRENCE-ID;TZID=America/New_York:20250113T080000\r\n\
+             DTSTART;TZID=America/New_York:20250113T080000\r\n\
+             STATUS:CANCELLED\r\n\
+             END:VEVENT\r\n",
+        );
+        let events = parse_calendar(&text);
+        assert_eq!(events.len(), 2);
+        assert_eq!(january(&events[0]), vec![10, 12, 14]);
+        assert_eq!(events[1].summary, "Daily (moved)");
+        assert_eq!(january(&events[1]), vec![11]);
+    }
+
+    #[test]
+    fn test_floating_time_follows_display_zone() {
+        let text = calendar(
+            "BEGIN:VEVENT\r\nUID:f\r\nDTSTART:20250301T230000\r\nDTEND:20250302T010000\r\nEND:VEVENT\r\n",
+        );
+        let event = &parse_calendar(&text)[0];
+        let tokyo = chrono_tz::Asia::Tokyo;
+        let occurrence = &event.occurrences(date(2025, 3, 1), date(2025, 3, 31), &tokyo)[0];
+        let OccurrenceTime::Timed { start, .. } = occurrence.time else {
+            panic!("timed event: {occurrence:?}");
+        };
+        assert_eq!(start.hour(), 23);
+        assert_eq!(occurrence.first_day(), date(2025, 3, 1));
+        assert_eq!(occurrence.last_day(), date(2025, 3, 2));
+
+        assert_eq!(parse_duration("-PT15M"), Some(TimeDelta::minutes(-15)));
+        assert_eq!(
+            parse_duration("P1W2DT3H"),
+            Some(TimeDelta::hours(9 * 24 + 3))
+        );
+        // Out of range instead of a panic
+        assert_eq!(parse_duration("P99999999999999W"), None);
+        assert_eq!(parse_duration("P100000000000DT200000000000H"), None);
+    }
+}
@@ -1,17 +1,33 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+mod alarm; // Recurring wall-clock alarms
+mod astronomy; // Sunrise, sunset and twilight (computed locally)
+mod audio; // Alarm sound playback (pw-play / paplay)
+mod caldav; // CalDAV calendar sync (cached for offline use)
 mod calendar; // Calendar module (renamed from time)
 pub mod config;
+mod events; // Calendar events (local ICS files)
+mod holidays; // Public holidays (embedded rules dataset)
 pub mod icons; // Centralized icon names (SSoT)
+mod ics; // iCalendar (RFC 5545) parser and recurrence expansion
 mod localize;
+mod location; // Weather location (offline gazetteer, system timezone)
 mod panel; // Panel UI module
 pub mod paths; // Portable asset location (XDG-compliant)
+mod pomodoro; // Pomodoro cycle (work / short break / long break)
 mod popup; // Popup UI module
+mod secrets; // Passwords from the Secret Service (desktop keyring)
+mod session; // Timer session persistence (XDG state dir)
+mod stopwatch; // Stopwatch with laps (monotonic clock)
 mod subscriptions; // Subscription management (time, timezone, wake-from-sleep)
 mod time; // Panel time formatting module (new)
-mod timer;
-mod weather;
+mod timer; // Countdown engine + Timer tab
+mod units; // Weather unit conversions (metric / imperial / mixed)
+mod weather; // Weather tab (current conditions and forecasts)
+mod weather_alerts; // Severe weather alerts (CAP / Atom feeds)
+mod weather_cache; // Last weather report (XDG cache dir)
+mod weather_provider; // Weather data sources (Open-Meteo)
 mod window;
 
 use window::Window;
@@ -38,23 +54,37 @@ pub enum Message {
 
     // Module envelopes
     Calendar(calendar::CalendarMessage),
+    Timer(timer::TimerMessage),
+    Weather(weather::WeatherMessage),
 
-    // Notifications (Placeholders for Phase 3.9)
-    /// Trigger a notification alert with sound
+    // Notifications
+    /// Show a desktop notification with the action buttons of its source
     TriggerNotification {
         message: String,
+        /// Expiration in seconds (0 = stay until dismissed)
         duration_secs: u64,
+        source: NotificationSource,
     },
-    /// Notification was dismissed (body click or X button)
-    NotificationDismissed,
+    /// Notification server accepted a notification and assigned it an ID
+    NotificationShown {
+        id: u32,
+        source: NotificationSource,
+    },
+    /// Notification was closed (expired, dismissed or closed after an action)
+    NotificationDismissed(u32),
     /// User clicked notification action button
-    NotificationAction(String),
+    NotificationAction {
+        id: u32,
+        action: String,
+    },
 
     // System
     OpenDateTimeSettings,
     Token(TokenUpdate),
     ConfigChanged(TimeAppletConfig),
     TimezoneUpdate(String),
+    /// System resumed from suspend (wall clock may have jumped)
+    WakeFromSleep,
 }
 
 // ============================================================================
@@ -100,6 +130,86 @@ impl Tab {
     }
 }
 
+// ============================================================================
+// Notification Sources
+// ============================================================================
+// Notifications are routed back to the feature that raised them, so action
+// buttons (snooze, restart, ...) know what they apply to.
+
+/// Feature that raised a desktop notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationSource {
+    /// Named countdown, by timer ID
+    Timer(u32),
+    Pomodoro,
+    /// Wall-clock alarm, by alarm ID
+    Alarm(u32),
+    /// Severe weather alert (each alert is notified once)
+    WeatherAlert,
+}
+
+impl NotificationSource {
+    /// Snooze the finished countdown (or ringing alarm) for a few minutes
+    pub const ACTION_SNOOZE: &'static str = "snooze";
+    /// Start the finished countdown again with the same duration
+    pub const ACTION_RESTART: &'static str = "restart";
+    /// Skip the Pomodoro phase that has just started
+    pub const ACTION_SKIP: &'static str = "skip";
+
+    /// Translated notification title
+    pub fn summary(&self) -> String {
+        match self {
+            NotificationSource::Timer(_) => crate::fl!("timer"),
+            NotificationSource::Pomodoro => crate::fl!("timer-mode-pomodoro"),
+            NotificationSource::Alarm(_) => crate::fl!("alarm"),
+            NotificationSource::WeatherAlert => crate::fl!("weather-alert"),
+        }
+    }
+
+    /// Icon shown in the notification
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            NotificationSource::WeatherAlert => icons::weather::ALERT,
+            _ => Tab::Timer.icon_name(),
+        }
+    }
+
+    /// Action buttons offered by notifications of this source (key, label)
+    pub fn actions(&self) -> Vec<(&'static str, String)> {
+        match self {
+            NotificationSource::Timer(_) => vec![
+                (Self::ACTION_SNOOZE, crate::fl!("notification-snooze")),
+                (Self::ACTION_RESTART, crate::fl!("notification-restart")),
+            ],
+            NotificationSource::Pomodoro => {
+                vec![(Self::ACTION_SKIP, crate::fl!("notification-skip"))]
+            }
+            NotificationSource::Alarm(_) => {
+                vec![(Self::ACTION_SNOOZE, crate::fl!("notification-snooze"))]
+            }
+            NotificationSource::WeatherAlert => Vec::new(),
+        }
+    }
+
+    /// Timer tab message an action of this source's notification triggers
+    ///
+    /// Alarm actions are handled by Window (alarms live in the config).
+    pub fn timer_message(&self, action: &str) -> Option<timer::TimerMessage> {
+        match (self, action) {
+            (NotificationSource::Timer(timer), Self::ACTION_SNOOZE) => {
+                Some(timer::TimerMessage::Snooze(*timer))
+            }
+            (NotificationSource::Timer(timer), Self::ACTION_RESTART) => {
+                Some(timer::TimerMessage::Restart(*timer))
+            }
+            (NotificationSource::Pomodoro, Self::ACTION_SKIP) => Some(
+                timer::TimerMessage::Pomodoro(pomodoro::PomodoroMessage::Skip),
+            ),
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // Application Entry Point (Neutral Messenger)
 // ============================================================================
@@ -111,3 +221,49 @@ pub fn run(config: TimeAppletConfig) -> cosmic::iced::Result {
 
     cosmic::applet::run::<Window>(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pomodoro::PomodoroMessage;
+    use crate::timer::TimerMessage;
+
+    #[test]
+    fn test_notification_actions() {
+        let keys = |source: NotificationSource| -> Vec<&str> {
+            source.actions().into_iter().map(|(key, _)| key).collect()
+        };
+        assert_eq!(keys(NotificationSource::Timer(1)), ["snooze", "restart"]);
+        assert_eq!(keys(NotificationSource::Pomodoro), ["skip"]);
+        assert_eq!(keys(NotificationSource::Alarm(1)), ["snooze"]);
+        assert!(keys(NotificationSource::WeatherAlert).is_empty());
+    }
+
+    #[test]
+    fn test_notification_action_routing() {
+        assert!(matches!(
+            NotificationSource::Timer(3).timer_message("snooze"),
+            Some(TimerMessage::Snooze(3))
+        ));
+        assert!(matches!(
+            NotificationSource::Timer(3).timer_message("restart"),
+            Some(TimerMessage::Restart(3))
+        ));
+        assert!(matches!(
+            NotificationSource::Pomodoro.timer_message("skip"),
+            Some(TimerMessage::Pomodoro(PomodoroMessage::Skip))
+        ));
+        // Unknown keys and keys of other sources do nothing
+        assert!(NotificationSource::Timer(3).timer_message("skip").is_none());
+        assert!(
+            NotificationSource::Pomodoro
+                .timer_message("default")
+                .is_none()
+        );
+        assert!(
+            NotificationSource::Alarm(1)
+                .timer_message("snooze")
+                .is_none()
+        );
+    }
+}
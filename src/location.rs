@@ -0,0 +1,196 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Weather location
+//!
+//! Resolves where the weather is fetched for, without any network lookup:
+//! coordinates set in the config win; otherwise the location is derived from
+//! the system timezone reported by `Message::TimezoneUpdate`. Cities can be
+//! picked by name from a gazetteer bundled in the binary
+//! (`data/gazetteer.tsv`, built from the tz database plus major cities).
+
+use std::sync::LazyLock;
+
+use crate::config::TimeAppletConfig;
+use crate::weather_provider::Coordinates;
+
+/// Maximum number of matches offered by the location search
+pub const SEARCH_LIMIT: usize = 6;
+
+/// Minimum query length before searching (one letter matches half the world)
+const MIN_QUERY_LEN: usize = 2;
+
+static GAZETTEER: LazyLock<Vec<City>> =
+    LazyLock::new(|| parse_gazetteer(include_str!("../data/gazetteer.tsv")));
+
+// ============================================================================
+// Places
+// ============================================================================
+
+/// A named location weather can be fetched for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    /// Display name ("Berlin, Germany")
+    pub name: String,
+    pub coordinates: Coordinates,
+}
+
+/// Gazetteer entry
+#[derive(Debug, Clone)]
+struct City {
+    name: String,
+    country: String,
+    coordinates: Coordinates,
+    /// IANA timezone the city lies in
+    timezone: String,
+}
+
+impl City {
+    fn place(&self) -> Place {
+        Place {
+            name: format!("{}, {}", self.name, self.country),
+            coordinates: self.coordinates,
+        }
+    }
+}
+
+/// Parse the tab-separated gazetteer, skipping comments and malformed lines
+fn parse_gazetteer(data: &str) -> Vec<City> {
+    data.lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            let country = fields.next()?;
+            let latitude = fields.next()?.parse().ok()?;
+            let longitude = fields.next()?.parse().ok()?;
+            let timezone = fields.next()?;
+            Some(City {
+                name: name.to_string(),
+                country: country.to_string(),
+                coordinates: Coordinates {
+                    latitude,
+                    longitude,
+                },
+                timezone: timezone.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Lowercase and strip common Latin diacritics, so "sao" finds "São Paulo"
+fn fold(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            '_' | '-' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Lookup
+// ============================================================================
+
+/// Location set in the config, if any
+///
+/// Without a display name, the coordinates themselves are shown.
+pub fn configured(config: &TimeAppletConfig) -> Option<Place> {
+    let coordinates = config.weather_coordinates()?;
+    let name = match config.weather_location_name.trim() {
+        "" => format!("{:.2}, {:.2}", coordinates.latitude, coordinates.longitude),
+        name => name.to_string(),
+    };
+    Some(Place { name, coordinates })
+}
+
+/// Approximate location for an IANA timezone (e.g. "Europe/Berlin" → Berlin)
+///
+/// Most zones are named after their main city, which is preferred over other
+/// gazetteer cities of the same zone. Zones without a city (e.g. "UTC")
+/// give `None`.
+pub fn from_timezone(timezone: &str) -> Option<Place> {
+    let zone_city = fold(timezone.rsplit('/').next().unwrap_or(timezone));
+    let cities: Vec<&City> = GAZETTEER
+        .iter()
+        .filter(|city| city.timezone == timezone)
+        .collect();
+    cities
+        .iter()
+        .find(|city| fold(&city.name) == zone_city)
+        .or(cities.first())
+        .map(|city| city.place())
+}
+
+/// Gazetteer cities matching `query`, name prefixes first
+pub fn search(query: &str) -> Vec<Place> {
+    let query = fold(query.trim());
+    if query.chars().count() < MIN_QUERY_LEN {
+        return Vec::new();
+    }
+
+    let (prefix, other): (Vec<&City>, Vec<&City>) = GAZETTEER
+        .iter()
+        .filter(|city| fold(&city.name).contains(&query))
+        .partition(|city| fold(&city.name).starts_with(&query));
+    prefix
+        .into_iter()
+        .chain(other)
+        .take(SEARCH_LIMIT)
+        .map(City::place)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gazetteer_is_valid() {
+        assert!(GAZETTEER.len() > 400);
+        for city in GAZETTEER.iter() {
+            assert!(
+                (-90.0..=90.0).contains(&city.coordinates.latitude),
+                "{city:?}"
+            );
+            assert!(
+                (-180.0..=180.0).contains(&city.coordinates.longitude),
+                "{city:?}"
+            );
+            assert!(city.timezone.contains('/'), "{city:?}");
+        }
+    }
+
+    #[test]
+    fn test_timezone_prefers_zone_city() {
+        let berlin = from_timezone("Europe/Berlin").unwrap();
+        assert_eq!(berlin.name, "Berlin, Germany");
+        assert!((berlin.coordinates.latitude - 52.5).abs() < 0.1);
+
+        let sao_paulo = from_timezone("America/Sao_Paulo").unwrap();
+        assert_eq!(sao_paulo.name, "São Paulo, Brazil");
+
+        let new_york = from_timezone("America/New_York").unwrap();
+        assert_eq!(new_york.name, "New York, United States");
+
+        assert_eq!(from_timezone("UTC"), None);
+    }
+
+    #[test]
+    fn test_search_ignores_case_and_accents() {
+        let results = search("SAO p");
+        assert_eq!(results[0].name, "São Paulo, Brazil");
+
+        assert_eq!(search("york")[0].name, "New York, United States");
+        assert!(search("x").is_empty());
+    }
+}
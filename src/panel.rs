@@ -4,7 +4,9 @@
 //! Panel UI module
 //!
 //! This module is responsible for rendering the applet button in the COSMIC panel.
-//! It handles both horizontal and vertical panel layouts.
+//! It handles both horizontal and vertical panel layouts, and shows the
+//! active Timer tab countdown next to (or instead of) the clock, and the
+//! current weather before it.
 //!
 //! The visual logic (sizes, spacing, alignment) was extracted from `time.rs`
 //! and is preserved as-is to maintain the approved UI design.
@@ -16,8 +18,8 @@ use cosmic::{
         Alignment, Length,
         widget::{column, row},
     },
-    iced_widget::{Column, horizontal_rule},
-    widget::{container, horizontal_space, vertical_space},
+    iced_widget::{Column, Row, horizontal_rule},
+    widget::{container, horizontal_space, icon, vertical_space},
 };
 use icu::{
     datetime::{
@@ -26,8 +28,95 @@ use icu::{
     locale::preferences::extensions::unicode::keywords::HourCycle,
 };
 
-use crate::config::TimeAppletConfig;
+use crate::config::{PanelTimerDisplay, TimeAppletConfig};
 use crate::time::PanelFormatter;
+use crate::timer::{PanelCountdown, format_duration};
+use crate::units::Units;
+use crate::weather_provider::CurrentConditions;
+
+/// Progress ring glyphs, from just started to almost finished
+const PROGRESS_RING: [&str; 5] = ["○", "◔", "◑", "◕", "●"];
+
+/// Marker shown in front of a paused countdown
+const PAUSED_MARKER: &str = "⏸";
+
+// ============================================================================
+// Countdown Helpers
+// ============================================================================
+
+/// Countdown to render, honoring `panel_timer_display`
+fn visible_countdown(
+    countdown: Option<PanelCountdown>,
+    config: &TimeAppletConfig,
+) -> Option<PanelCountdown> {
+    countdown.filter(|_| config.panel_timer_display != PanelTimerDisplay::Hidden)
+}
+
+/// Check if the clock is rendered alongside `countdown`
+fn shows_clock(countdown: Option<PanelCountdown>, config: &TimeAppletConfig) -> bool {
+    countdown.is_none() || config.panel_timer_display != PanelTimerDisplay::InsteadOfClock
+}
+
+/// Panel pieces for a countdown: optional ring, pause marker and remaining time
+fn countdown_pieces(countdown: PanelCountdown, config: &TimeAppletConfig) -> Vec<String> {
+    let mut pieces = Vec::new();
+    if config.panel_timer_progress {
+        let step = (countdown.progress * (PROGRESS_RING.len() - 1) as f32).round() as usize;
+        pieces.push(PROGRESS_RING[step.min(PROGRESS_RING.len() - 1)].to_string());
+    }
+    if countdown.paused {
+        pieces.push(PAUSED_MARKER.to_string());
+    }
+    pieces.push(format_duration(countdown.remaining));
+    pieces
+}
+
+// ============================================================================
+// Weather Helpers
+// ============================================================================
+
+/// Current weather as shown in the panel button
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelWeather {
+    /// Symbolic condition icon name
+    pub icon: &'static str,
+    /// Temperature in the configured unit ("21°C")
+    pub temperature: String,
+}
+
+impl PanelWeather {
+    pub fn new(current: &CurrentConditions, units: Units) -> Self {
+        Self {
+            icon: crate::weather::condition_icon(current.condition, current.is_day),
+            temperature: units.temperature.format(current.temperature),
+        }
+    }
+}
+
+/// Panel elements for the weather, honoring `panel_weather_display`
+fn weather_elements<'a, M: 'a>(
+    weather: Option<&PanelWeather>,
+    config: &TimeAppletConfig,
+    applet: &applet::Context,
+) -> Vec<Element<'a, M>> {
+    let Some(weather) = weather else {
+        return Vec::new();
+    };
+    let display = config.panel_weather_display;
+
+    let mut elements = Vec::new();
+    if display.shows_icon() {
+        let condition_icon: icon::Icon = icon::from_name(weather.icon)
+            .size(applet.suggested_size(true).0)
+            .symbolic(true)
+            .into();
+        elements.push(condition_icon.into());
+    }
+    if display.shows_temperature() {
+        elements.push(applet.text(weather.temperature.clone()).into());
+    }
+    elements
+}
 
 // ============================================================================
 // Panel View Functions
@@ -42,56 +131,85 @@ pub fn vertical_layout<'a, M: 'a>(
     now: &DateTime<FixedOffset>,
     config: &TimeAppletConfig,
     applet: &applet::Context,
+    countdown: Option<PanelCountdown>,
+    weather: Option<&PanelWeather>,
 ) -> Element<'a, M> {
-    let elements: Vec<Element<'a, M>> =
-        if let Some(strftime) = formatter.maybe_strftime(now, &config.format_strftime) {
-            strftime
-                .split_whitespace()
-                .map(|piece| applet.text(piece.to_owned()).into())
-                .collect()
-        } else {
-            let mut elements = Vec::new();
-            let date = now.naive_local();
-            let datetime = crate::calendar::create_datetime(&date, now);
-            let mut prefs = DateTimeFormatterPreferences::from(formatter.locale().clone());
-            prefs.hour_cycle = Some(if config.military_time {
-                HourCycle::H23
-            } else {
-                HourCycle::H12
-            });
+    let countdown = visible_countdown(countdown, config);
 
-            if config.show_date_in_top_panel {
-                let formatted_date = DateTimeFormatter::try_new(prefs, fieldsets::MD::medium())
-                    .unwrap()
-                    .format(&datetime)
-                    .to_string();
-
-                for p in formatted_date.split_whitespace() {
-                    elements.push(applet.text(p.to_owned()).into());
-                }
-                elements.push(
-                    horizontal_rule(2)
-                        .width(applet.suggested_size(true).0)
-                        .into(),
-                );
-            }
-            let mut fs = fieldsets::T::medium();
-            if !config.show_seconds {
-                fs = fs.with_time_precision(TimePrecision::Minute);
+    let mut elements: Vec<Element<'a, M>> = weather_elements(weather, config, applet);
+    if !elements.is_empty() {
+        elements.push(
+            horizontal_rule(2)
+                .width(applet.suggested_size(true).0)
+                .into(),
+        );
+    }
+    if let Some(countdown) = countdown {
+        for piece in countdown_pieces(countdown, config) {
+            for p in piece.split(':') {
+                elements.push(applet.text(p.to_owned()).into());
             }
-            let formatted_time = DateTimeFormatter::try_new(prefs, fs)
+        }
+    }
+
+    let clock: Vec<Element<'a, M>> = if !shows_clock(countdown, config) {
+        Vec::new()
+    } else if let Some(strftime) = formatter.maybe_strftime(now, &config.format_strftime) {
+        strftime
+            .split_whitespace()
+            .map(|piece| applet.text(piece.to_owned()).into())
+            .collect()
+    } else {
+        let mut elements = Vec::new();
+        let date = now.naive_local();
+        let datetime = crate::calendar::create_datetime(&date, now);
+        let mut prefs = DateTimeFormatterPreferences::from(formatter.locale().clone());
+        prefs.hour_cycle = Some(if config.military_time {
+            HourCycle::H23
+        } else {
+            HourCycle::H12
+        });
+
+        if config.show_date_in_top_panel {
+            let formatted_date = DateTimeFormatter::try_new(prefs, fieldsets::MD::medium())
                 .unwrap()
                 .format(&datetime)
                 .to_string();
 
-            // todo: split using formatToParts when it is implemented
-            // https://github.com/unicode-org/icu4x/issues/4936#issuecomment-2128812667
-            for p in formatted_time.split_whitespace().flat_map(|s| s.split(':')) {
+            for p in formatted_date.split_whitespace() {
                 elements.push(applet.text(p.to_owned()).into());
             }
+            elements.push(
+                horizontal_rule(2)
+                    .width(applet.suggested_size(true).0)
+                    .into(),
+            );
+        }
+        let mut fs = fieldsets::T::medium();
+        if !config.show_seconds {
+            fs = fs.with_time_precision(TimePrecision::Minute);
+        }
+        let formatted_time = DateTimeFormatter::try_new(prefs, fs)
+            .unwrap()
+            .format(&datetime)
+            .to_string();
 
-            elements
-        };
+        // todo: split using formatToParts when it is implemented
+        // https://github.com/unicode-org/icu4x/issues/4936#issuecomment-2128812667
+        for p in formatted_time.split_whitespace().flat_map(|s| s.split(':')) {
+            elements.push(applet.text(p.to_owned()).into());
+        }
+
+        elements
+    };
+    if countdown.is_some() && !clock.is_empty() {
+        elements.push(
+            horizontal_rule(2)
+                .width(applet.suggested_size(true).0)
+                .into(),
+        );
+    }
+    elements.extend(clock);
 
     let date_time_col = Column::with_children(elements)
         .align_x(Alignment::Center)
@@ -117,7 +235,10 @@ pub fn horizontal_layout<'a, M: 'a>(
     now: &DateTime<FixedOffset>,
     config: &TimeAppletConfig,
     applet: &applet::Context,
+    countdown: Option<PanelCountdown>,
+    weather: Option<&PanelWeather>,
 ) -> Element<'a, M> {
+    let countdown = visible_countdown(countdown, config);
     let formatted_date =
         if let Some(strftime) = formatter.maybe_strftime(now, &config.format_strftime) {
             strftime
@@ -162,9 +283,25 @@ pub fn horizontal_layout<'a, M: 'a>(
             }
         };
 
+    let mut content = row!().spacing(8).align_y(Alignment::Center);
+    let weather = weather_elements(weather, config, applet);
+    if !weather.is_empty() {
+        content = content.push(
+            Row::with_children(weather)
+                .spacing(4)
+                .align_y(Alignment::Center),
+        );
+    }
+    if let Some(countdown) = countdown {
+        content = content.push(applet.text(countdown_pieces(countdown, config).join(" ")));
+    }
+    if shows_clock(countdown, config) {
+        content = content.push(applet.text(formatted_date));
+    }
+
     Element::from(
         row!(
-            applet.text(formatted_date),
+            content,
             container(vertical_space().height(Length::Fixed(
                 (applet.suggested_size(true).1 + 2 * applet.suggested_padding(true).1) as f32
             )))
@@ -177,17 +314,20 @@ pub fn horizontal_layout<'a, M: 'a>(
 ///
 /// This is the primary entry point for rendering the applet button in the panel.
 /// It delegates to either `horizontal_layout()` or `vertical_layout()` based on
-/// the panel orientation.
+/// the panel orientation. `countdown` is the active Timer tab countdown and
+/// `weather` the current weather, if any.
 pub fn view<'a, M: 'a>(
     formatter: &PanelFormatter,
     now: &DateTime<FixedOffset>,
     config: &TimeAppletConfig,
     applet: &applet::Context,
     horizontal: bool,
+    countdown: Option<PanelCountdown>,
+    weather: Option<&PanelWeather>,
 ) -> Element<'a, M> {
     if horizontal {
-        horizontal_layout(formatter, now, config, applet)
+        horizontal_layout(formatter, now, config, applet, countdown, weather)
     } else {
-        vertical_layout(formatter, now, config, applet)
+        vertical_layout(formatter, now, config, applet, countdown, weather)
     }
 }
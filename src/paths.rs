@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Obtém o diretório de dados do applet seguindo XDG Base Directory Specification.
 ///
@@ -49,6 +49,53 @@ fn get_data_dir() -> Option<PathBuf> {
     None
 }
 
+/// Obtém o diretório de estado do applet seguindo XDG Base Directory Specification.
+///
+/// Usado para dados de sessão que devem sobreviver a reinicializações
+/// (ex: timers em andamento), mas que não são configuração do usuário.
+///
+/// Prioridade:
+/// 1. $XDG_STATE_HOME/cosmic-applet-timeplus (Usuário)
+/// 2. ~/.local/state/cosmic-applet-timeplus (Fallback usuário)
+pub fn get_state_dir() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local/state")))
+        .map(|state| state.join("cosmic-applet-timeplus"))
+}
+
+/// Obtém o diretório de cache do applet seguindo XDG Base Directory Specification.
+///
+/// Usado para dados baixados que podem ser descartados a qualquer momento
+/// (ex: última previsão do tempo, exibida enquanto não há rede).
+///
+/// Prioridade:
+/// 1. $XDG_CACHE_HOME/cosmic-applet-timeplus (Usuário)
+/// 2. ~/.cache/cosmic-applet-timeplus (Fallback usuário)
+pub fn get_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cache")))
+        .map(|cache| cache.join("cosmic-applet-timeplus"))
+}
+
+/// Grava `value` em JSON no arquivo `path`, criando o diretório se preciso.
+///
+/// Falhas são registradas no log, não propagadas: estado e cache podem ser
+/// perdidos sem prejuízo. `context` inicia a mensagem de erro (ex:
+/// "[Timer] Failed to save session").
+pub fn write_json<T: serde::Serialize>(path: &Path, value: &T, context: &str) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let data = serde_json::to_string(value).map_err(std::io::Error::other)?;
+            std::fs::write(path, data)
+        });
+
+    if let Err(err) = result {
+        tracing::warn!("{} {}: {}", context, path.display(), err);
+    }
+}
+
 /// Obtém o caminho de um asset de áudio.
 ///
 /// # Argumentos
@@ -86,6 +133,29 @@ mod tests {
         assert!(path.to_str().unwrap().contains("alarm.ogg"));
     }
 
+    #[test]
+    fn test_state_dir_is_applet_specific() {
+        if let Some(state_dir) = get_state_dir() {
+            assert!(state_dir.ends_with("cosmic-applet-timeplus"));
+        }
+    }
+
+    #[test]
+    fn test_cache_dir_is_applet_specific() {
+        if let Some(cache_dir) = get_cache_dir() {
+            assert!(cache_dir.ends_with("cosmic-applet-timeplus"));
+        }
+    }
+
+    #[test]
+    fn test_write_json_creates_directory() {
+        let dir = env::temp_dir().join(format!("timeplus-paths-{}", std::process::id()));
+        let path = dir.join("nested").join("value.json");
+        write_json(&path, &vec![1, 2, 3], "[Test] Cannot write");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1,2,3]");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_respects_env_var_override() {
         // SAFETY: Test function, setting env var before spawning threads
@@ -0,0 +1,363 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Pomodoro module
+//!
+//! Work / short break / long break cycle built on top of the countdown
+//! engine from `timer.rs`. Phases advance automatically when their countdown
+//! reaches zero; a long break follows every N completed work phases.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use cosmic::{
+    Element,
+    iced::{Alignment, Length},
+    widget::{button, column, container, icon, row, text},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::TimeAppletConfig;
+use crate::timer::{Countdown, CountdownStatus, format_duration};
+use crate::{fl, icons};
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+/// Phase lengths and cycle size, resolved from `TimeAppletConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PomodoroSettings {
+    pub work: TimeDelta,
+    pub short_break: TimeDelta,
+    pub long_break: TimeDelta,
+    pub cycles_before_long_break: u32,
+}
+
+impl PomodoroSettings {
+    /// Resolve settings from config, clamping zero values to one
+    pub fn from_config(config: &TimeAppletConfig) -> Self {
+        let minutes = |value: u32| TimeDelta::minutes(i64::from(value.max(1)));
+        Self {
+            work: minutes(config.pomodoro_work_minutes),
+            short_break: minutes(config.pomodoro_short_break_minutes),
+            long_break: minutes(config.pomodoro_long_break_minutes),
+            cycles_before_long_break: config.pomodoro_cycles_before_long_break.max(1),
+        }
+    }
+
+    /// Length of the given phase
+    pub fn duration(&self, phase: PomodoroPhase) -> TimeDelta {
+        match phase {
+            PomodoroPhase::Work => self.work,
+            PomodoroPhase::ShortBreak => self.short_break,
+            PomodoroPhase::LongBreak => self.long_break,
+        }
+    }
+}
+
+// ============================================================================
+// Pomodoro State Machine
+// ============================================================================
+
+/// Pomodoro phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    /// Translated phase name
+    pub fn label(&self) -> String {
+        match self {
+            PomodoroPhase::Work => fl!("pomodoro-work"),
+            PomodoroPhase::ShortBreak => fl!("pomodoro-short-break"),
+            PomodoroPhase::LongBreak => fl!("pomodoro-long-break"),
+        }
+    }
+}
+
+/// Pomodoro cycle state
+#[derive(Debug, Clone)]
+pub struct PomodoroState {
+    phase: PomodoroPhase,
+    /// Work phases completed since the last long break
+    completed: u32,
+    countdown: Countdown,
+}
+
+impl Default for PomodoroState {
+    fn default() -> Self {
+        Self {
+            phase: PomodoroPhase::Work,
+            completed: 0,
+            countdown: Countdown::new(TimeDelta::zero()),
+        }
+    }
+}
+
+impl PomodoroState {
+    /// Restore a cycle saved by the session module
+    pub fn restore(phase: PomodoroPhase, completed: u32, countdown: Countdown) -> Self {
+        Self {
+            phase,
+            completed,
+            countdown,
+        }
+    }
+
+    /// Current phase
+    pub fn phase(&self) -> PomodoroPhase {
+        self.phase
+    }
+
+    /// Work phases completed since the last long break
+    pub fn completed(&self) -> u32 {
+        self.completed
+    }
+
+    /// Countdown of the current phase
+    pub fn countdown(&self) -> &Countdown {
+        &self.countdown
+    }
+
+    /// 1-based position of the current work phase within the cycle
+    pub fn cycle(&self, settings: &PomodoroSettings) -> u32 {
+        let cycle = match self.phase {
+            PomodoroPhase::Work => self.completed + 1,
+            _ => self.completed.max(1),
+        };
+        cycle.min(settings.cycles_before_long_break)
+    }
+
+    /// Time left in the current phase (full phase length while idle)
+    pub fn remaining(&self, settings: &PomodoroSettings, now: DateTime<Utc>) -> TimeDelta {
+        match self.countdown.status() {
+            CountdownStatus::Idle => settings.duration(self.phase),
+            _ => self.countdown.remaining(now),
+        }
+    }
+
+    /// Start the current phase from its full length
+    pub fn start(&mut self, settings: &PomodoroSettings, now: DateTime<Utc>) {
+        self.countdown.start(settings.duration(self.phase), now);
+    }
+
+    /// Update pomodoro state based on message
+    pub fn update(
+        &mut self,
+        message: PomodoroMessage,
+        settings: &PomodoroSettings,
+        now: DateTime<Utc>,
+    ) {
+        match message {
+            // Restarting the phase is the same as starting it from its full length
+            PomodoroMessage::Start | PomodoroMessage::RestartPhase => self.start(settings, now),
+            PomodoroMessage::Pause => self.countdown.pause(now),
+            PomodoroMessage::Resume => self.countdown.resume(now),
+            PomodoroMessage::Skip => self.skip(settings, now),
+            PomodoroMessage::Reset => self.reset(),
+        }
+    }
+
+    /// Move on to the next phase, keeping it running if the current one was
+    pub fn skip(&mut self, settings: &PomodoroSettings, now: DateTime<Utc>) {
+        let was_running = self.countdown.is_running();
+        self.advance(settings);
+        if was_running {
+            self.start(settings, now);
+        }
+    }
+
+    /// Back to the first work phase of a fresh cycle
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advance the cycle, returning the phase that has just finished
+    ///
+    /// The next phase is started automatically.
+    pub fn tick(
+        &mut self,
+        settings: &PomodoroSettings,
+        now: DateTime<Utc>,
+    ) -> Option<PomodoroPhase> {
+        if !self.countdown.tick(now) {
+            return None;
+        }
+        let finished = self.phase;
+        self.advance(settings);
+        self.start(settings, now);
+        Some(finished)
+    }
+
+    /// Settle a phase that expired while the applet was not running
+    ///
+    /// Returns the finished phase; the next one is left idle.
+    pub fn expire(
+        &mut self,
+        settings: &PomodoroSettings,
+        now: DateTime<Utc>,
+    ) -> Option<PomodoroPhase> {
+        if !self.countdown.tick(now) {
+            return None;
+        }
+        let finished = self.phase;
+        self.advance(settings);
+        Some(finished)
+    }
+
+    /// Switch to the phase following the current one (left idle)
+    fn advance(&mut self, settings: &PomodoroSettings) {
+        self.phase = match self.phase {
+            PomodoroPhase::Work => {
+                self.completed += 1;
+                if self.completed >= settings.cycles_before_long_break {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                }
+            }
+            PomodoroPhase::ShortBreak => PomodoroPhase::Work,
+            PomodoroPhase::LongBreak => {
+                self.completed = 0;
+                PomodoroPhase::Work
+            }
+        };
+        self.countdown.reset();
+    }
+}
+
+// ============================================================================
+// Pomodoro Messages
+// ============================================================================
+
+/// Messages for pomodoro interactions
+#[derive(Debug, Clone)]
+pub enum PomodoroMessage {
+    Start,
+    Pause,
+    Resume,
+    /// Jump to the next phase
+    Skip,
+    /// Start the current phase over from its full length
+    RestartPhase,
+    /// Back to the first work phase of a fresh cycle
+    Reset,
+}
+
+// ============================================================================
+// Public View Function
+// ============================================================================
+
+/// Renders the Pomodoro mode: phase, remaining time, cycle counter and controls
+pub fn view_pomodoro<'a>(
+    state: &PomodoroState,
+    settings: &PomodoroSettings,
+    now: DateTime<Utc>,
+) -> Element<'a, PomodoroMessage> {
+    let remaining = text::title1(format_duration(state.remaining(settings, now)));
+
+    let cycle = text::body(fl!(
+        "pomodoro-cycle",
+        current = state.cycle(settings),
+        total = settings.cycles_before_long_break
+    ));
+
+    let primary = match state.countdown.status() {
+        CountdownStatus::Idle | CountdownStatus::Finished => {
+            button::suggested(fl!("timer-start")).on_press(PomodoroMessage::Start)
+        }
+        CountdownStatus::Running { .. } => {
+            button::standard(fl!("timer-pause")).on_press(PomodoroMessage::Pause)
+        }
+        CountdownStatus::Paused { .. } => {
+            button::suggested(fl!("timer-resume")).on_press(PomodoroMessage::Resume)
+        }
+    };
+
+    let controls = row()
+        .push(primary)
+        .push(button::standard(fl!("timer-reset")).on_press(PomodoroMessage::Reset))
+        .push(
+            button::icon(icon::from_name(icons::timer::RESTART))
+                .padding(8)
+                .on_press(PomodoroMessage::RestartPhase),
+        )
+        .push(
+            button::icon(icon::from_name(icons::timer::SKIP))
+                .padding(8)
+                .on_press(PomodoroMessage::Skip),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8);
+
+    column()
+        .push(
+            container(
+                column()
+                    .push(text::heading(state.phase.label()))
+                    .push(remaining)
+                    .push(cycle)
+                    .align_x(Alignment::Center),
+            )
+            .center_x(Length::Fill),
+        )
+        .push(controls)
+        .spacing(12)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> PomodoroSettings {
+        PomodoroSettings {
+            work: TimeDelta::minutes(25),
+            short_break: TimeDelta::minutes(5),
+            long_break: TimeDelta::minutes(15),
+            cycles_before_long_break: 2,
+        }
+    }
+
+    #[test]
+    fn test_cycle_reaches_long_break() {
+        let settings = settings();
+        let mut state = PomodoroState::default();
+        let mut now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        state.start(&settings, now);
+
+        let mut phases = Vec::new();
+        for _ in 0..4 {
+            now += settings.duration(state.phase());
+            phases.push(state.tick(&settings, now).unwrap());
+        }
+
+        assert_eq!(
+            phases,
+            [
+                PomodoroPhase::Work,
+                PomodoroPhase::ShortBreak,
+                PomodoroPhase::Work,
+                PomodoroPhase::LongBreak,
+            ]
+        );
+        assert_eq!(state.phase(), PomodoroPhase::Work);
+        assert_eq!(state.cycle(&settings), 1);
+        assert!(state.countdown().is_running());
+    }
+
+    #[test]
+    fn test_skip_keeps_idle_state() {
+        let settings = settings();
+        let mut state = PomodoroState::default();
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        state.skip(&settings, now);
+        assert_eq!(state.phase(), PomodoroPhase::ShortBreak);
+        assert_eq!(state.countdown().status(), CountdownStatus::Idle);
+        assert_eq!(state.remaining(&settings, now), settings.short_break);
+    }
+}
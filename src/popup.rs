@@ -20,12 +20,39 @@ use cosmic::{
 };
 use icu::locale::Locale;
 
-use crate::{Message, Tab, calendar::CalendarState, config::TimeAppletConfig, fl};
+use crate::{
+    Message, Tab,
+    alarm::{AlarmForm, AlarmSchedule},
+    astronomy::SunTimes,
+    calendar::{CalendarState, GridOptions, SecondaryCalendar},
+    config::TimeAppletConfig,
+    fl,
+    location::{self, Place},
+    pomodoro::PomodoroSettings,
+    timer::{AlarmsView, TimerState},
+    units::Units,
+    weather::WeatherState,
+};
 
 // ============================================================================
 // Popup View Function
 // ============================================================================
 
+/// Feature state rendered by the tabs
+pub struct TabStates<'a> {
+    pub calendar: &'a CalendarState,
+    pub timer: &'a TimerState,
+    pub alarm_form: &'a AlarmForm,
+    pub alarm_schedule: &'a AlarmSchedule,
+    pub weather: &'a WeatherState,
+    /// Weather location derived from the system timezone, if any
+    pub timezone_place: Option<&'a Place>,
+    /// Sun times of today at the weather location
+    pub sun_today: Option<SunTimes>,
+    /// Sun times of the calendar's selected day at the weather location
+    pub sun_selected: Option<SunTimes>,
+}
+
 /// Main view function for the popup window
 ///
 /// This is the primary entry point for rendering the applet popup.
@@ -38,7 +65,7 @@ use crate::{Message, Tab, calendar::CalendarState, config::TimeAppletConfig, fl}
 /// the exact visual logic (spacing, layout, structure).
 pub fn view<'a>(
     locale: &'a Locale,
-    calendar_state: &'a CalendarState,
+    states: TabStates<'a>,
     now: &'a DateTime<FixedOffset>,
     config: &'a TimeAppletConfig,
     selected_tab: Tab,
@@ -61,11 +88,50 @@ pub fn view<'a>(
     // Select view based on active tab
     let tab_content = match selected_tab {
         Tab::Calendar => {
-            crate::calendar::view_calendar(locale, calendar_state, now, config.first_day_of_week)
-                .map(Message::Calendar)
+            let options = GridOptions {
+                first_day_of_week: config.first_day_of_week,
+                moon_offset: config.show_moon_phases.then(|| *now.offset()),
+                week_numbers: config.show_week_numbers,
+                secondary_calendar: SecondaryCalendar::new(&config.secondary_calendar, locale),
+                agenda_days: config.agenda_days,
+            };
+            crate::calendar::view_calendar(
+                locale,
+                states.calendar,
+                now,
+                &options,
+                states.sun_selected.as_ref(),
+            )
+            .map(Message::Calendar)
+        }
+        Tab::Weather => {
+            // A configured location overrides the one from the timezone
+            let configured = location::configured(config);
+            let automatic = configured.is_none() && states.timezone_place.is_some();
+            crate::weather::view_weather(
+                locale,
+                states.weather,
+                configured.or_else(|| states.timezone_place.cloned()),
+                automatic,
+                Units::resolve(config, locale),
+                states.sun_today.as_ref(),
+                now.to_utc(),
+            )
+            .map(Message::Weather)
         }
-        Tab::Weather => crate::weather::view_weather(),
-        Tab::Timer => crate::timer::view_timer(),
+        Tab::Timer => crate::timer::view_timer(
+            states.timer,
+            AlarmsView {
+                locale,
+                form: states.alarm_form,
+                alarms: &config.alarms,
+                schedule: states.alarm_schedule,
+                first_day_of_week: config.first_day_of_week,
+            },
+            &PomodoroSettings::from_config(config),
+            now.to_utc(),
+        )
+        .map(Message::Timer),
     };
 
     // Footer with settings button
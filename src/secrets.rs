@@ -0,0 +1,93 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Secret Service passwords
+//!
+//! Passwords live in the desktop keyring (GNOME Keyring, KWallet, KeePassXC…)
+//! and are read over the freedesktop Secret Service D-Bus API, matched by
+//! attributes. Store the CalDAV password with:
+//!
+//! ```sh
+//! secret-tool store --label="TimePlus CalDAV" \
+//!     application cosmic-applet-timeplus url <caldav_url> username <caldav_username>
+//! ```
+
+use std::collections::HashMap;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+/// `application` attribute of the applet's keyring items
+const APPLICATION: &str = "cosmic-applet-timeplus";
+
+/// A secret: (session, parameters, value, content type)
+type Secret = (OwnedObjectPath, Vec<u8>, Vec<u8>, String);
+
+#[zbus::proxy(
+    interface = "org.freedesktop.Secret.Service",
+    default_service = "org.freedesktop.secrets",
+    default_path = "/org/freedesktop/secrets"
+)]
+trait SecretService {
+    fn open_session(
+        &self,
+        algorithm: &str,
+        input: &Value<'_>,
+    ) -> zbus::Result<(OwnedValue, OwnedObjectPath)>;
+
+    fn search_items(
+        &self,
+        attributes: HashMap<&str, &str>,
+    ) -> zbus::Result<(Vec<OwnedObjectPath>, Vec<OwnedObjectPath>)>;
+
+    fn get_secrets(
+        &self,
+        items: &[ObjectPath<'_>],
+        session: &ObjectPath<'_>,
+    ) -> zbus::Result<HashMap<OwnedObjectPath, Secret>>;
+}
+
+/// Password of `username` at `url`, if an unlocked keyring has one
+///
+/// Failures (no Secret Service running, locked keyring) are logged; the
+/// caller then connects without a password.
+pub async fn lookup_password(url: &str, username: &str) -> Option<String> {
+    match find_password(url, username).await {
+        Ok(password) => password,
+        Err(err) => {
+            tracing::warn!("[Secrets] Cannot read the keyring: {}", err);
+            None
+        }
+    }
+}
+
+async fn find_password(url: &str, username: &str) -> zbus::Result<Option<String>> {
+    let connection = zbus::Connection::session().await?;
+    let service = SecretServiceProxy::new(&connection).await?;
+
+    let attributes = HashMap::from([
+        ("application", APPLICATION),
+        ("url", url),
+        ("username", username),
+    ]);
+    let (unlocked, locked) = service.search_items(attributes).await?;
+    let Some(item) = unlocked.first() else {
+        if locked.is_empty() {
+            tracing::info!("[Secrets] No password stored for {} at {}", username, url);
+        } else {
+            tracing::warn!(
+                "[Secrets] Keyring is locked, password for {} unavailable",
+                url
+            );
+        }
+        return Ok(None);
+    };
+
+    // "plain": the secret is not encrypted on the (local) session bus
+    let (_, session) = service.open_session("plain", &Value::from("")).await?;
+    let secrets = service
+        .get_secrets(&[item.as_ref()], &session.as_ref())
+        .await?;
+    Ok(secrets
+        .into_values()
+        .next()
+        .and_then(|(_, _, value, _)| String::from_utf8(value).ok()))
+}
@@ -0,0 +1,254 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Timer session persistence
+//!
+//! Saves the Timer tab state to `$XDG_STATE_HOME/cosmic-applet-timeplus` so
+//! named timers survive panel restarts and reboots. Running countdowns are
+//! stored as absolute wall-clock deadlines (not remaining seconds), which lets
+//! `TimerState::catch_up` settle whatever expired while the applet was down.
+
+use chrono::{DateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::pomodoro::{PomodoroPhase, PomodoroState};
+use crate::stopwatch::Stopwatch;
+use crate::timer::{Countdown, CountdownStatus, NamedTimer, TimerMode, TimerState};
+
+/// File name of the session inside the state directory
+const SESSION_FILE: &str = "timers.json";
+
+// ============================================================================
+// Serialized Representation
+// ============================================================================
+
+/// Countdown status with timestamps as Unix milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum SavedStatus {
+    Idle,
+    Running { deadline_ms: i64 },
+    Paused { remaining_ms: i64 },
+    Finished,
+}
+
+impl From<CountdownStatus> for SavedStatus {
+    fn from(status: CountdownStatus) -> Self {
+        match status {
+            CountdownStatus::Idle => SavedStatus::Idle,
+            CountdownStatus::Running { deadline } => SavedStatus::Running {
+                deadline_ms: deadline.timestamp_millis(),
+            },
+            CountdownStatus::Paused { remaining } => SavedStatus::Paused {
+                remaining_ms: remaining.num_milliseconds(),
+            },
+            CountdownStatus::Finished => SavedStatus::Finished,
+        }
+    }
+}
+
+impl SavedStatus {
+    /// Convert back, treating out-of-range timestamps as idle
+    fn restore(self) -> CountdownStatus {
+        match self {
+            SavedStatus::Idle => CountdownStatus::Idle,
+            SavedStatus::Running { deadline_ms } => DateTime::from_timestamp_millis(deadline_ms)
+                .map_or(CountdownStatus::Idle, |deadline| CountdownStatus::Running {
+                    deadline,
+                }),
+            SavedStatus::Paused { remaining_ms } => CountdownStatus::Paused {
+                remaining: TimeDelta::try_milliseconds(remaining_ms).unwrap_or_default(),
+            },
+            SavedStatus::Finished => CountdownStatus::Finished,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedCountdown {
+    duration_ms: i64,
+    #[serde(flatten)]
+    status: SavedStatus,
+}
+
+impl From<&Countdown> for SavedCountdown {
+    fn from(countdown: &Countdown) -> Self {
+        Self {
+            duration_ms: countdown.duration().num_milliseconds(),
+            status: countdown.status().into(),
+        }
+    }
+}
+
+impl SavedCountdown {
+    fn restore(self) -> Countdown {
+        Countdown::restore(
+            TimeDelta::try_milliseconds(self.duration_ms).unwrap_or_default(),
+            self.status.restore(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedPomodoro {
+    phase: PomodoroPhase,
+    completed: u32,
+    countdown: SavedCountdown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedTimer {
+    id: u32,
+    label: String,
+    countdown: SavedCountdown,
+}
+
+/// Persisted Timer tab state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TimerSession {
+    mode: TimerMode,
+    input: String,
+    /// Sessions from single-countdown versions have no list: start empty
+    #[serde(default)]
+    timers: Vec<SavedTimer>,
+    /// Older sessions don't have it: continue after the highest saved ID
+    #[serde(default)]
+    next_id: u32,
+    pomodoro: SavedPomodoro,
+}
+
+impl From<&TimerState> for TimerSession {
+    fn from(state: &TimerState) -> Self {
+        Self {
+            mode: state.mode,
+            input: state.input.clone(),
+            timers: state
+                .timers
+                .iter()
+                .map(|timer| SavedTimer {
+                    id: timer.id,
+                    label: timer.label.clone(),
+                    countdown: (&timer.countdown).into(),
+                })
+                .collect(),
+            next_id: state.next_id,
+            pomodoro: SavedPomodoro {
+                phase: state.pomodoro.phase(),
+                completed: state.pomodoro.completed(),
+                countdown: state.pomodoro.countdown().into(),
+            },
+        }
+    }
+}
+
+impl From<TimerSession> for TimerState {
+    fn from(session: TimerSession) -> Self {
+        let next_id = session
+            .timers
+            .iter()
+            .map(|timer| timer.id.saturating_add(1))
+            .fold(session.next_id.max(1), u32::max);
+        Self {
+            mode: session.mode,
+            input: session.input,
+            timers: session
+                .timers
+                .into_iter()
+                .map(|timer| NamedTimer {
+                    id: timer.id,
+                    label: timer.label,
+                    countdown: timer.countdown.restore(),
+                })
+                .collect(),
+            next_id,
+            label_input: String::new(),
+            pomodoro: PomodoroState::restore(
+                session.pomodoro.phase,
+                session.pomodoro.completed,
+                session.pomodoro.countdown.restore(),
+            ),
+            // Monotonic instants do not survive the process
+            stopwatch: Stopwatch::default(),
+        }
+    }
+}
+
+// ============================================================================
+// Load / Save
+// ============================================================================
+
+fn session_path() -> Option<PathBuf> {
+    crate::paths::get_state_dir().map(|dir| dir.join(SESSION_FILE))
+}
+
+/// Load the saved timer state, if any
+///
+/// A missing or unreadable session is not an error: the applet simply starts
+/// with fresh timers.
+pub fn load() -> Option<TimerState> {
+    let path = session_path()?;
+    let data = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<TimerSession>(&data) {
+        Ok(session) => {
+            tracing::info!("[Timer] Restored session from {}", path.display());
+            Some(session.into())
+        }
+        Err(err) => {
+            tracing::warn!(
+                "[Timer] Ignoring corrupt session {}: {}",
+                path.display(),
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Save the timer state, logging (not propagating) failures
+pub fn save(state: &TimerState) {
+    let Some(path) = session_path() else {
+        tracing::warn!("[Timer] No state directory available, session not saved");
+        return;
+    };
+
+    crate::paths::write_json(
+        &path,
+        &TimerSession::from(state),
+        "[Timer] Failed to save session",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_deadline_roundtrip() {
+        let deadline = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let state = TimerState {
+            timers: vec![NamedTimer {
+                id: 3,
+                label: "Laundry".to_string(),
+                countdown: Countdown::restore(
+                    TimeDelta::minutes(5),
+                    CountdownStatus::Running { deadline },
+                ),
+            }],
+            next_id: 7,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&TimerSession::from(&state)).unwrap();
+        let restored: TimerState = serde_json::from_str::<TimerSession>(&json).unwrap().into();
+
+        let timer = &restored.timers[0];
+        assert_eq!((timer.id, timer.label.as_str()), (3, "Laundry"));
+        assert_eq!(
+            timer.countdown.status(),
+            CountdownStatus::Running { deadline }
+        );
+        assert_eq!(timer.countdown.duration(), TimeDelta::minutes(5));
+        assert_eq!(restored.next_id, 7);
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Stopwatch module
+//!
+//! Stopwatch with lap capture for the Timer tab. Unlike countdowns, elapsed
+//! time is measured with the monotonic `tokio::time::Instant` rather than the
+//! wall clock refreshed by `Message::Tick`, so timezone changes and clock
+//! adjustments never corrupt a measurement (and time spent suspended is not
+//! counted). For the same reason the stopwatch is not part of the persisted
+//! timer session: instants are meaningless across processes.
+
+use std::time::Duration;
+
+use cosmic::{
+    Element,
+    iced::{Alignment, Length},
+    widget::{button, column, container, icon, row, scrollable, text},
+};
+use tokio::time::Instant;
+
+use crate::{fl, icons};
+
+/// Height of the lap list before it starts scrolling
+const LAP_LIST_HEIGHT: f32 = 160.0;
+
+// ============================================================================
+// Stopwatch Engine
+// ============================================================================
+
+/// A captured lap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lap {
+    /// Time since the previous lap (or the start for the first one)
+    pub split: Duration,
+    /// Time since the start
+    pub total: Duration,
+}
+
+/// Stopwatch state
+///
+/// Like the countdown engine, every operation takes the current instant as
+/// input so the state machine stays testable.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    /// Set while running: instant the current run started
+    started: Option<Instant>,
+    /// Time accumulated by previous runs (before the last stop)
+    accumulated: Duration,
+    laps: Vec<Lap>,
+}
+
+impl Stopwatch {
+    /// Check if the stopwatch is currently running
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Captured laps, oldest first
+    pub fn laps(&self) -> &[Lap] {
+        &self.laps
+    }
+
+    /// Total elapsed time at `now`
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.accumulated
+            + self.started.map_or(Duration::ZERO, |started| {
+                now.saturating_duration_since(started)
+            })
+    }
+
+    /// Start, or continue after a stop
+    pub fn start(&mut self, now: Instant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    /// Stop, keeping the elapsed time and laps
+    pub fn stop(&mut self, now: Instant) {
+        self.accumulated = self.elapsed(now);
+        self.started = None;
+    }
+
+    /// Back to zero, clearing laps
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Capture a lap (only while running)
+    pub fn lap(&mut self, now: Instant) {
+        if !self.is_running() {
+            return;
+        }
+        let total = self.elapsed(now);
+        let previous = self.laps.last().map_or(Duration::ZERO, |lap| lap.total);
+        self.laps.push(Lap {
+            split: total.saturating_sub(previous),
+            total,
+        });
+    }
+
+    /// Laps as tab-separated text, one per line, for the clipboard
+    pub fn laps_text(&self) -> String {
+        self.laps
+            .iter()
+            .enumerate()
+            .map(|(index, lap)| {
+                format!(
+                    "{}\t{}\t{}",
+                    fl!("stopwatch-lap", number = index + 1),
+                    format_elapsed(lap.split),
+                    format_elapsed(lap.total)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Update stopwatch state based on message
+    pub fn update(&mut self, message: StopwatchMessage, now: Instant) {
+        match message {
+            StopwatchMessage::Start => self.start(now),
+            StopwatchMessage::Stop => self.stop(now),
+            StopwatchMessage::Reset => self.reset(),
+            StopwatchMessage::Lap => self.lap(now),
+            // Clipboard access needs a runtime task, issued by Window
+            StopwatchMessage::CopyLaps => {}
+        }
+    }
+}
+
+/// Format elapsed time as `MM:SS.t`, or `H:MM:SS.t` when it spans hours
+///
+/// Truncates (a stopwatch never shows time that has not elapsed yet).
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let tenths = elapsed.subsec_millis() / 100;
+    format!("{}.{tenths}", format_seconds(elapsed))
+}
+
+/// Format elapsed time as `MM:SS`, or `H:MM:SS` when it spans hours
+///
+/// Used while running: the popup only refreshes on the one-second tick, so
+/// tenths would just jump by whatever passed since the previous tick.
+fn format_seconds(elapsed: Duration) -> String {
+    let total = elapsed.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+// ============================================================================
+// Stopwatch Messages
+// ============================================================================
+
+/// Messages for stopwatch interactions
+#[derive(Debug, Clone)]
+pub enum StopwatchMessage {
+    Start,
+    Stop,
+    Reset,
+    Lap,
+    /// Copy the lap list to the clipboard
+    CopyLaps,
+}
+
+// ============================================================================
+// Public View Function
+// ============================================================================
+
+/// Renders the Stopwatch mode: elapsed time, controls and lap list
+pub fn view_stopwatch<'a>(stopwatch: &Stopwatch, now: Instant) -> Element<'a, StopwatchMessage> {
+    let elapsed = stopwatch.elapsed(now);
+    let elapsed_text = if stopwatch.is_running() {
+        format_seconds(elapsed)
+    } else {
+        format_elapsed(elapsed)
+    };
+
+    let primary = if stopwatch.is_running() {
+        button::standard(fl!("stopwatch-stop")).on_press(StopwatchMessage::Stop)
+    } else if elapsed.is_zero() {
+        button::suggested(fl!("timer-start")).on_press(StopwatchMessage::Start)
+    } else {
+        button::suggested(fl!("timer-resume")).on_press(StopwatchMessage::Start)
+    };
+
+    let controls = row()
+        .push(primary)
+        .push(
+            button::standard(fl!("stopwatch-lap-button"))
+                .on_press_maybe(stopwatch.is_running().then_some(StopwatchMessage::Lap)),
+        )
+        .push(
+            button::standard(fl!("timer-reset"))
+                .on_press_maybe((!elapsed.is_zero()).then_some(StopwatchMessage::Reset)),
+        )
+        .push(
+            button::icon(icon::from_name(icons::timer::COPY))
+                .padding(8)
+                .on_press_maybe(
+                    (!stopwatch.laps().is_empty()).then_some(StopwatchMessage::CopyLaps),
+                ),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8);
+
+    let mut content = column()
+        .push(container(text::title1(elapsed_text)).center_x(Length::Fill))
+        .push(controls)
+        .spacing(12);
+
+    if !stopwatch.laps().is_empty() {
+        let lap_row = |label: String, split: String, total: String| {
+            row()
+                .push(text::body(label).width(Length::Fill))
+                .push(text::body(split).width(Length::Fill))
+                .push(text::body(total).width(Length::Fill))
+        };
+
+        // Newest lap first, like most stopwatches
+        let mut laps = column().spacing(4);
+        for (index, lap) in stopwatch.laps().iter().enumerate().rev() {
+            laps = laps.push(lap_row(
+                fl!("stopwatch-lap", number = index + 1),
+                format_elapsed(lap.split),
+                format_elapsed(lap.total),
+            ));
+        }
+
+        content = content
+            .push(
+                row()
+                    .push(text::caption(fl!("stopwatch-lap-header")).width(Length::Fill))
+                    .push(text::caption(fl!("stopwatch-split")).width(Length::Fill))
+                    .push(text::caption(fl!("stopwatch-total")).width(Length::Fill)),
+            )
+            .push(container(scrollable(laps)).max_height(LAP_LIST_HEIGHT));
+    }
+
+    content.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_laps_and_stop_accumulate() {
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        let mut stopwatch = Stopwatch::default();
+
+        stopwatch.start(at(0));
+        stopwatch.lap(at(1_500));
+        stopwatch.stop(at(2_000));
+        // Laps are ignored and time does not advance while stopped
+        stopwatch.lap(at(5_000));
+        assert_eq!(stopwatch.elapsed(at(9_000)), Duration::from_millis(2_000));
+
+        stopwatch.start(at(10_000));
+        stopwatch.lap(at(11_000));
+
+        assert_eq!(
+            stopwatch.laps(),
+            [
+                Lap {
+                    split: Duration::from_millis(1_500),
+                    total: Duration::from_millis(1_500),
+                },
+                Lap {
+                    split: Duration::from_millis(1_500),
+                    total: Duration::from_millis(3_000),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed_truncates() {
+        assert_eq!(format_elapsed(Duration::from_millis(61_990)), "01:01.9");
+        assert_eq!(format_elapsed(Duration::from_secs(3_723)), "1:02:03.0");
+        assert_eq!(format_seconds(Duration::from_millis(61_990)), "01:01");
+    }
+}
@@ -4,7 +4,8 @@
 //! Subscription management module
 //!
 //! This module handles all heavy subscription logic for the applet,
-//! including time ticking, timezone updates, and wake-from-sleep detection.
+//! including time ticking, timezone updates, wake-from-sleep detection and
+//! desktop notification signals.
 
 use chrono::Timelike;
 use cosmic::{
@@ -142,7 +143,7 @@ async fn wake_from_sleep(output: &mut mpsc::Sender<Message>) -> zbus::Result<()>
     while let Some(property) = proxy.receive_prepare_for_sleep().await?.next().await {
         let waking = !property.args()?.start();
         if waking {
-            let _ = output.send(Message::Tick).await;
+            let _ = output.send(Message::WakeFromSleep).await;
         }
     }
     Ok(())
@@ -150,7 +151,8 @@ async fn wake_from_sleep(output: &mut mpsc::Sender<Message>) -> zbus::Result<()>
 
 /// Wake from sleep subscription
 ///
-/// Detects when the system wakes from sleep and triggers a time update.
+/// Detects when the system wakes from sleep and triggers a time update and
+/// alarm rescheduling.
 pub fn wake_from_sleep_subscription() -> Subscription<Message> {
     Subscription::run_with_id(
         "wake-from-suspend-sub",
@@ -161,3 +163,192 @@ pub fn wake_from_sleep_subscription() -> Subscription<Message> {
         }),
     )
 }
+
+// ============================================================================
+// Notification Subscription
+// ============================================================================
+
+/// Signals of the freedesktop notification server (session bus)
+///
+/// Any service owning `org.freedesktop.Notifications` works, including a mock
+/// server on a private session bus (via `DBUS_SESSION_BUS_ADDRESS`).
+#[zbus::proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Notifications {
+    #[zbus(signal)]
+    fn action_invoked(&self, id: u32, action_key: String) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn notification_closed(&self, id: u32, reason: u32) -> zbus::Result<()>;
+}
+
+/// Forward notification action and close signals of the session bus to the
+/// applet
+async fn notification_events(output: &mut mpsc::Sender<Message>) -> zbus::Result<()> {
+    let conn = zbus::Connection::session().await?;
+    forward_notification_signals(&conn, output).await
+}
+
+/// Forward notification action and close signals seen on `conn`
+async fn forward_notification_signals(
+    conn: &zbus::Connection,
+    output: &mut mpsc::Sender<Message>,
+) -> zbus::Result<()> {
+    let proxy = NotificationsProxy::new(conn).await?;
+    let mut actions = proxy.receive_action_invoked().await?;
+    let mut closed = proxy.receive_notification_closed().await?;
+
+    loop {
+        let message = tokio::select! {
+            Some(signal) = actions.next() => {
+                let args = signal.args()?;
+                Message::NotificationAction {
+                    id: args.id,
+                    action: args.action_key,
+                }
+            }
+            Some(signal) = closed.next() => Message::NotificationDismissed(signal.args()?.id),
+            else => break,
+        };
+        output
+            .send(message)
+            .await
+            .map_err(|e| zbus::Error::InputOutput(std::sync::Arc::new(std::io::Error::other(e))))?;
+    }
+    Ok(())
+}
+
+/// Notification subscription
+///
+/// Routes D-Bus `ActionInvoked` / `NotificationClosed` signals back into
+/// `Message::NotificationAction` / `Message::NotificationDismissed`.
+pub fn notification_subscription() -> Subscription<Message> {
+    Subscription::run_with_id(
+        "notification-sub",
+        stream::channel(1, |mut output| async move {
+            'retry: loop {
+                match notification_events(&mut output).await {
+                    Ok(()) => break 'retry,
+                    Err(err) => {
+                        tracing::error!(
+                            ?err,
+                            "Notification signal listener failed; retrying in one minute"
+                        );
+                        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    }
+                }
+            }
+
+            std::future::pending().await
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::process::{Child, Command, Stdio};
+
+    const PATH: &str = "/org/freedesktop/Notifications";
+    const INTERFACE: &str = "org.freedesktop.Notifications";
+
+    /// Private session bus, stopped on drop
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        /// Start `dbus-daemon` (`None` if it isn't installed)
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            std::io::BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+
+        async fn connect(&self) -> zbus::Connection {
+            zbus::connection::Builder::address(self.address.as_str())
+                .unwrap()
+                .build()
+                .await
+                .unwrap()
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    async fn next(messages: &mut mpsc::Receiver<Message>) -> Message {
+        time::timeout(time::Duration::from_secs(5), messages.next())
+            .await
+            .expect("signal forwarded")
+            .expect("listener running")
+    }
+
+    #[tokio::test]
+    async fn test_signals_from_mock_notification_server() {
+        // Needs dbus-daemon; without it there is nothing to test against
+        let Some(bus) = PrivateBus::start() else {
+            return;
+        };
+        // Mock org.freedesktop.Notifications: owns the name and emits signals
+        let server = bus.connect().await;
+        server.request_name(INTERFACE).await.unwrap();
+
+        let client = bus.connect().await;
+        let (mut output, mut messages) = mpsc::channel(4);
+        let listener =
+            tokio::spawn(async move { forward_notification_signals(&client, &mut output).await });
+        // Let the listener subscribe before signalling
+        time::sleep(time::Duration::from_millis(200)).await;
+
+        server
+            .emit_signal(
+                None::<()>,
+                PATH,
+                INTERFACE,
+                "ActionInvoked",
+                &(7u32, "snooze"),
+            )
+            .await
+            .unwrap();
+        server
+            .emit_signal(
+                None::<()>,
+                PATH,
+                INTERFACE,
+                "NotificationClosed",
+                &(7u32, 2u32),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            next(&mut messages).await,
+            Message::NotificationAction { id: 7, action } if action == "snooze"
+        ));
+        assert!(matches!(
+            next(&mut messages).await,
+            Message::NotificationDismissed(7)
+        ));
+        listener.abort();
+    }
+}
@@ -1,19 +1,699 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+//! Timer module
+//!
+//! Countdown engine and Timer tab view (countdown, Pomodoro, stopwatch and
+//! alarm modes).
+//! Countdowns are tracked as wall-clock deadlines and advanced by the shared
+//! `Message::Tick` from the time subscription, so no extra timer task is needed.
+
+use chrono::{DateTime, TimeDelta, Utc};
 use cosmic::{
     Element,
     applet::padded_control,
     cosmic_theme::Spacing,
     iced::{Alignment, Length},
-    widget::{Space, column, container, divider, icon, row, text},
+    widget::{Space, button, column, container, divider, icon, row, text, text_input},
 };
+use icu::locale::Locale;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::alarm::{Alarm, AlarmForm, AlarmMessage, AlarmSchedule};
+use crate::pomodoro::{PomodoroMessage, PomodoroPhase, PomodoroSettings, PomodoroState};
+use crate::stopwatch::{Stopwatch, StopwatchMessage};
+use crate::{NotificationSource, Tab};
+use crate::{fl, icons};
+
+/// Duration pre-filled in the entry field (minutes)
+const DEFAULT_DURATION_MINUTES: i64 = 5;
+
+/// Quick duration presets shown below the entry field (minutes)
+const QUICK_PRESETS: [i64; 4] = [1, 5, 10, 15];
+
+/// Length of a snooze requested from a notification (minutes)
+const SNOOZE_MINUTES: i64 = 5;
+
+// ============================================================================
+// Countdown Engine
+// ============================================================================
+
+/// Lifecycle of a countdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountdownStatus {
+    /// Not started yet (or reset)
+    Idle,
+    /// Counting down towards a wall-clock deadline
+    Running { deadline: DateTime<Utc> },
+    /// Paused with the given time left
+    Paused { remaining: TimeDelta },
+    /// Reached zero
+    Finished,
+}
+
+/// Countdown engine
+///
+/// Pure state machine: every operation takes the current time as input,
+/// which keeps it independent from the UI and easy to test.
+#[derive(Debug, Clone)]
+pub struct Countdown {
+    duration: TimeDelta,
+    status: CountdownStatus,
+}
+
+impl Countdown {
+    /// Create an idle countdown with the given duration
+    pub fn new(duration: TimeDelta) -> Self {
+        Self {
+            duration,
+            status: CountdownStatus::Idle,
+        }
+    }
+
+    /// Restore a countdown saved by the session module
+    pub fn restore(duration: TimeDelta, status: CountdownStatus) -> Self {
+        Self { duration, status }
+    }
+
+    /// Configured duration
+    pub fn duration(&self) -> TimeDelta {
+        self.duration
+    }
+
+    /// Current lifecycle status
+    pub fn status(&self) -> CountdownStatus {
+        self.status
+    }
+
+    /// Check if the countdown is currently running
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, CountdownStatus::Running { .. })
+    }
+
+    /// Start from the full duration (also restarts a finished countdown)
+    pub fn start(&mut self, duration: TimeDelta, now: DateTime<Utc>) {
+        self.duration = duration;
+        self.status = CountdownStatus::Running {
+            deadline: now + duration,
+        };
+    }
+
+    /// Start again from the full configured duration
+    pub fn restart(&mut self, now: DateTime<Utc>) {
+        self.start(self.duration, now);
+    }
+
+    /// Run for `extra` from `now`, keeping the configured duration for later
+    /// restarts
+    pub fn snooze(&mut self, extra: TimeDelta, now: DateTime<Utc>) {
+        self.status = CountdownStatus::Running {
+            deadline: now + extra,
+        };
+    }
+
+    /// Pause a running countdown, keeping the time left
+    pub fn pause(&mut self, now: DateTime<Utc>) {
+        if let CountdownStatus::Running { deadline } = self.status {
+            self.status = CountdownStatus::Paused {
+                remaining: (deadline - now).max(TimeDelta::zero()),
+            };
+        }
+    }
+
+    /// Resume a paused countdown
+    pub fn resume(&mut self, now: DateTime<Utc>) {
+        if let CountdownStatus::Paused { remaining } = self.status {
+            self.status = CountdownStatus::Running {
+                deadline: now + remaining,
+            };
+        }
+    }
+
+    /// Go back to idle, keeping the configured duration
+    pub fn reset(&mut self) {
+        self.status = CountdownStatus::Idle;
+    }
+
+    /// Time left at `now`
+    pub fn remaining(&self, now: DateTime<Utc>) -> TimeDelta {
+        match self.status {
+            CountdownStatus::Idle => self.duration,
+            CountdownStatus::Running { deadline } => (deadline - now).max(TimeDelta::zero()),
+            CountdownStatus::Paused { remaining } => remaining,
+            CountdownStatus::Finished => TimeDelta::zero(),
+        }
+    }
+
+    /// Fraction of the duration already elapsed at `now` (0.0 to 1.0)
+    pub fn progress(&self, now: DateTime<Utc>) -> f32 {
+        let total = self.duration.num_milliseconds();
+        if total <= 0 {
+            return 0.0;
+        }
+        let elapsed = total - self.remaining(now).num_milliseconds();
+        (elapsed as f32 / total as f32).clamp(0.0, 1.0)
+    }
+
+    /// Advance the countdown, returning `true` when it has just finished
+    pub fn tick(&mut self, now: DateTime<Utc>) -> bool {
+        match self.status {
+            CountdownStatus::Running { deadline } if deadline <= now => {
+                self.status = CountdownStatus::Finished;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// ============================================================================
+// Duration Helpers
+// ============================================================================
+
+/// Parse a user-entered duration
+///
+/// Accepted forms: `25` (minutes), `25:00` (minutes:seconds) and
+/// `1:30:00` (hours:minutes:seconds). Zero-length durations are rejected.
+pub fn parse_duration(input: &str) -> Option<TimeDelta> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    let numbers: Vec<i64> = parts
+        .iter()
+        .map(|part| part.trim().parse::<i64>().ok().filter(|n| *n >= 0))
+        .collect::<Option<_>>()?;
+
+    let seconds = match numbers.as_slice() {
+        [minutes] => minutes.checked_mul(60)?,
+        [minutes, seconds] if *seconds < 60 => minutes.checked_mul(60)? + seconds,
+        [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => {
+            hours.checked_mul(3600)? + minutes * 60 + seconds
+        }
+        _ => return None,
+    };
+
+    (seconds > 0)
+        .then(|| TimeDelta::try_seconds(seconds))
+        .flatten()
+}
+
+/// Format a duration as `MM:SS`, or `H:MM:SS` when it spans hours
+pub fn format_duration(duration: TimeDelta) -> String {
+    // Round up so a countdown shows 00:01 until it really reaches zero
+    let total = (duration.num_milliseconds().max(0) + 999) / 1000;
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+// ============================================================================
+// Timer State Management
+// ============================================================================
+
+/// Timer tab mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerMode {
+    #[default]
+    Countdown,
+    Pomodoro,
+    Stopwatch,
+    Alarms,
+}
+
+impl TimerMode {
+    /// Translated mode name
+    pub fn label(&self) -> String {
+        match self {
+            TimerMode::Countdown => fl!("timer-mode-countdown"),
+            TimerMode::Pomodoro => fl!("timer-mode-pomodoro"),
+            TimerMode::Stopwatch => fl!("timer-mode-stopwatch"),
+            TimerMode::Alarms => fl!("timer-mode-alarms"),
+        }
+    }
+}
+
+/// Something that happened during a tick and should be announced to the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimerEvent {
+    CountdownFinished {
+        id: u32,
+        label: String,
+    },
+    PomodoroPhaseFinished {
+        finished: PomodoroPhase,
+        next: PomodoroPhase,
+    },
+}
 
-use crate::fl;
-use crate::{Message, Tab, icons};
+impl TimerEvent {
+    /// Feature the notification for this event belongs to
+    pub fn notification_source(&self) -> NotificationSource {
+        match self {
+            TimerEvent::CountdownFinished { id, .. } => NotificationSource::Timer(*id),
+            TimerEvent::PomodoroPhaseFinished { .. } => NotificationSource::Pomodoro,
+        }
+    }
+
+    /// Notification text for this event
+    pub fn notification_body(&self) -> String {
+        match self {
+            TimerEvent::CountdownFinished { label, .. } => {
+                fl!("timer-finished-body", label = label.as_str())
+            }
+            TimerEvent::PomodoroPhaseFinished { finished, next } => fl!(
+                "pomodoro-phase-finished-body",
+                finished = finished.label(),
+                next = next.label()
+            ),
+        }
+    }
+}
+
+/// Active countdown summary rendered in the panel button
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelCountdown {
+    pub remaining: TimeDelta,
+    /// Fraction of the duration already elapsed (0.0 to 1.0)
+    pub progress: f32,
+    pub paused: bool,
+}
+
+/// A countdown with a user-given name ("tea", "build", ...)
+#[derive(Debug, Clone)]
+pub struct NamedTimer {
+    /// Stable identifier, used to route notification actions
+    pub id: u32,
+    pub label: String,
+    pub countdown: Countdown,
+}
+
+/// State management for the Timer tab
+#[derive(Debug, Clone)]
+pub struct TimerState {
+    pub mode: TimerMode,
+    /// Concurrent countdowns, in creation order
+    pub timers: Vec<NamedTimer>,
+    /// ID of the next timer; never reused, so a notification left over from a
+    /// removed timer can't act on a newer one
+    pub next_id: u32,
+    /// Raw text of the duration entry field
+    pub input: String,
+    /// Raw text of the new timer's label field
+    pub label_input: String,
+    pub pomodoro: PomodoroState,
+    pub stopwatch: Stopwatch,
+}
 
-/// Timer tab placeholder view
-pub fn view_timer<'a>() -> Element<'a, Message> {
+impl Default for TimerState {
+    fn default() -> Self {
+        Self {
+            mode: TimerMode::default(),
+            timers: Vec::new(),
+            next_id: 1,
+            input: DEFAULT_DURATION_MINUTES.to_string(),
+            label_input: String::new(),
+            pomodoro: PomodoroState::default(),
+            stopwatch: Stopwatch::default(),
+        }
+    }
+}
+
+impl TimerState {
+    /// Check if the time subscription must tick every second
+    pub fn needs_per_second_ticks(&self) -> bool {
+        self.timers.iter().any(|timer| timer.countdown.is_running())
+            || self.pomodoro.countdown().is_running()
+            || self.stopwatch.is_running()
+    }
+
+    /// Countdown to show in the panel: the running one ending first, else a paused one
+    pub fn panel_countdown(&self, now: DateTime<Utc>) -> Option<PanelCountdown> {
+        self.timers
+            .iter()
+            .map(|timer| &timer.countdown)
+            .chain([self.pomodoro.countdown()])
+            .filter(|countdown| {
+                matches!(
+                    countdown.status(),
+                    CountdownStatus::Running { .. } | CountdownStatus::Paused { .. }
+                )
+            })
+            .min_by_key(|countdown| (!countdown.is_running(), countdown.remaining(now)))
+            .map(|countdown| PanelCountdown {
+                remaining: countdown.remaining(now),
+                progress: countdown.progress(now),
+                paused: !countdown.is_running(),
+            })
+    }
+
+    /// Advance running countdowns, returning what has just finished
+    pub fn tick(&mut self, settings: &PomodoroSettings, now: DateTime<Utc>) -> Vec<TimerEvent> {
+        let mut events = self.tick_timers(now);
+        if let Some(finished) = self.pomodoro.tick(settings, now) {
+            events.push(TimerEvent::PomodoroPhaseFinished {
+                finished,
+                next: self.pomodoro.phase(),
+            });
+        }
+        events
+    }
+
+    /// Settle countdowns that expired while the applet was not running
+    ///
+    /// Unlike `tick`, an expired Pomodoro phase does not start the next one:
+    /// the cycle waits for the user instead of running unattended.
+    pub fn catch_up(&mut self, settings: &PomodoroSettings, now: DateTime<Utc>) -> Vec<TimerEvent> {
+        let mut events = self.tick_timers(now);
+        if let Some(finished) = self.pomodoro.expire(settings, now) {
+            events.push(TimerEvent::PomodoroPhaseFinished {
+                finished,
+                next: self.pomodoro.phase(),
+            });
+        }
+        events
+    }
+
+    /// Advance the named timers, returning those that have just finished
+    fn tick_timers(&mut self, now: DateTime<Utc>) -> Vec<TimerEvent> {
+        self.timers
+            .iter_mut()
+            .filter_map(|timer| {
+                timer
+                    .countdown
+                    .tick(now)
+                    .then(|| TimerEvent::CountdownFinished {
+                        id: timer.id,
+                        label: timer.label.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Create a timer from the entry fields and start it right away
+    fn add_timer(&mut self, duration: TimeDelta, now: DateTime<Utc>) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let label = match self.label_input.trim() {
+            "" => fl!("timer-default-label", number = id),
+            label => label.to_string(),
+        };
+        let mut countdown = Countdown::new(duration);
+        countdown.start(duration, now);
+
+        tracing::info!("[Timer] Added timer #{} '{}'", id, label);
+        self.timers.push(NamedTimer {
+            id,
+            label,
+            countdown,
+        });
+        self.label_input.clear();
+    }
+
+    /// Countdown of the timer with the given ID
+    fn countdown_mut(&mut self, id: u32) -> Option<&mut Countdown> {
+        self.timers
+            .iter_mut()
+            .find(|timer| timer.id == id)
+            .map(|timer| &mut timer.countdown)
+    }
+
+    /// Update timer state based on message
+    pub fn update(
+        &mut self,
+        message: TimerMessage,
+        settings: &PomodoroSettings,
+        now: DateTime<Utc>,
+    ) {
+        match message {
+            TimerMessage::SelectMode(mode) => {
+                self.mode = mode;
+            }
+            TimerMessage::DurationInput(input) => {
+                self.input = input;
+            }
+            TimerMessage::LabelInput(label) => {
+                self.label_input = label;
+            }
+            TimerMessage::Preset(minutes) => {
+                self.input = minutes.to_string();
+                self.add_timer(TimeDelta::minutes(minutes), now);
+            }
+            TimerMessage::Add => match parse_duration(&self.input) {
+                Some(duration) => self.add_timer(duration, now),
+                None => tracing::warn!("[Timer] Ignoring invalid duration '{}'", self.input),
+            },
+            TimerMessage::Pause(id) => {
+                if let Some(countdown) = self.countdown_mut(id) {
+                    countdown.pause(now);
+                }
+            }
+            TimerMessage::Resume(id) => {
+                if let Some(countdown) = self.countdown_mut(id) {
+                    countdown.resume(now);
+                }
+            }
+            TimerMessage::Snooze(id) => {
+                if let Some(countdown) = self.countdown_mut(id) {
+                    countdown.snooze(TimeDelta::minutes(SNOOZE_MINUTES), now);
+                }
+            }
+            TimerMessage::Restart(id) => {
+                if let Some(countdown) = self.countdown_mut(id) {
+                    countdown.restart(now);
+                }
+            }
+            TimerMessage::Remove(id) => self.timers.retain(|timer| timer.id != id),
+            TimerMessage::Pomodoro(msg) => self.pomodoro.update(msg, settings, now),
+            // Monotonic clock: wall-clock `now` would be skewed by clock changes
+            TimerMessage::Stopwatch(msg) => self.stopwatch.update(msg, Instant::now()),
+            // Alarms live in the config, which only Window can write
+            TimerMessage::Alarm(_) => {}
+        }
+    }
+}
+
+// ============================================================================
+// Timer Messages
+// ============================================================================
+
+/// Messages for timer interactions
+///
+/// Per-timer messages carry the `NamedTimer::id` they apply to.
+#[derive(Debug, Clone)]
+pub enum TimerMessage {
+    SelectMode(TimerMode),
+    DurationInput(String),
+    LabelInput(String),
+    /// Add a timer of the given minutes and start it right away
+    Preset(i64),
+    /// Add a timer from the entry fields and start it
+    Add,
+    Pause(u32),
+    Resume(u32),
+    /// Run the timer again for a few minutes (notification action)
+    Snooze(u32),
+    /// Run the timer again with its previous duration
+    Restart(u32),
+    Remove(u32),
+    Pomodoro(PomodoroMessage),
+    Stopwatch(StopwatchMessage),
+    Alarm(AlarmMessage),
+}
+
+impl TimerMessage {
+    /// Check if the message changes state the session file keeps (the entry
+    /// fields are saved along with the next such change)
+    pub fn changes_session(&self) -> bool {
+        matches!(
+            self,
+            TimerMessage::SelectMode(_)
+                | TimerMessage::Preset(_)
+                | TimerMessage::Add
+                | TimerMessage::Pause(_)
+                | TimerMessage::Resume(_)
+                | TimerMessage::Snooze(_)
+                | TimerMessage::Restart(_)
+                | TimerMessage::Remove(_)
+                | TimerMessage::Pomodoro(_)
+        )
+    }
+
+    /// Check if the message controls a countdown, Pomodoro or the stopwatch,
+    /// which silences ringing alarms (typing in the entry fields doesn't)
+    pub fn stops_sound(&self) -> bool {
+        match self {
+            TimerMessage::Stopwatch(msg) => !matches!(msg, StopwatchMessage::CopyLaps),
+            TimerMessage::SelectMode(_) => false,
+            msg => msg.changes_session(),
+        }
+    }
+}
+
+// ============================================================================
+// View Helpers
+// ============================================================================
+
+/// Translated status line for a countdown
+pub fn status_label(status: CountdownStatus) -> String {
+    match status {
+        CountdownStatus::Idle => fl!("timer-status-idle"),
+        CountdownStatus::Running { .. } => fl!("timer-status-running"),
+        CountdownStatus::Paused { .. } => fl!("timer-status-paused"),
+        CountdownStatus::Finished => fl!("timer-status-finished"),
+    }
+}
+
+/// Translated status line for the stopwatch
+fn stopwatch_status_label(stopwatch: &Stopwatch) -> String {
+    if stopwatch.is_running() {
+        fl!("timer-status-running")
+    } else if stopwatch.elapsed(Instant::now()).is_zero() {
+        fl!("timer-status-idle")
+    } else {
+        fl!("timer-status-paused")
+    }
+}
+
+/// Row of buttons switching between timer modes
+fn mode_selector<'a>(active: TimerMode) -> Element<'a, TimerMessage> {
+    let mut modes = row().spacing(8);
+    for mode in [
+        TimerMode::Countdown,
+        TimerMode::Pomodoro,
+        TimerMode::Stopwatch,
+        TimerMode::Alarms,
+    ] {
+        modes = modes.push(if mode == active {
+            button::suggested(mode.label())
+        } else {
+            button::text(mode.label()).on_press(TimerMessage::SelectMode(mode))
+        });
+    }
+    modes.into()
+}
+
+/// One row of the timer list: label, status, remaining time and controls
+fn timer_row<'a>(timer: &NamedTimer, now: DateTime<Utc>) -> Element<'a, TimerMessage> {
+    let id = timer.id;
+    let primary = match timer.countdown.status() {
+        CountdownStatus::Running { .. } => {
+            button::icon(icon::from_name(icons::timer::PAUSE)).on_press(TimerMessage::Pause(id))
+        }
+        CountdownStatus::Paused { .. } => {
+            button::icon(icon::from_name(icons::timer::START)).on_press(TimerMessage::Resume(id))
+        }
+        CountdownStatus::Idle | CountdownStatus::Finished => {
+            button::icon(icon::from_name(icons::timer::START)).on_press(TimerMessage::Restart(id))
+        }
+    };
+
+    row()
+        .push(
+            column()
+                .push(text::heading(timer.label.clone()))
+                .push(text::caption(status_label(timer.countdown.status())))
+                .width(Length::Fill),
+        )
+        .push(text::title3(format_duration(
+            timer.countdown.remaining(now),
+        )))
+        .push(primary.padding(8))
+        .push(
+            button::icon(icon::from_name(icons::timer::RESTART))
+                .padding(8)
+                .on_press(TimerMessage::Restart(id)),
+        )
+        .push(
+            button::icon(icon::from_name(icons::timer::DELETE))
+                .padding(8)
+                .on_press(TimerMessage::Remove(id)),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+}
+
+/// Countdown mode content: timer list, then the new timer entry and presets
+fn view_countdowns<'a>(
+    timer_state: &'a TimerState,
+    now: DateTime<Utc>,
+) -> Element<'a, TimerMessage> {
+    let mut list = column().spacing(8);
+    if timer_state.timers.is_empty() {
+        list = list.push(text::body(fl!("timer-empty")));
+    }
+    for timer in &timer_state.timers {
+        list = list.push(timer_row(timer, now));
+    }
+
+    let entry = row()
+        .push(
+            text_input(
+                fl!("timer-label-placeholder"),
+                timer_state.label_input.as_str(),
+            )
+            .on_input(TimerMessage::LabelInput)
+            .width(Length::Fill),
+        )
+        .push(
+            text_input(
+                fl!("timer-duration-placeholder"),
+                timer_state.input.as_str(),
+            )
+            .on_input(TimerMessage::DurationInput)
+            .width(Length::Fixed(100.0)),
+        )
+        .spacing(8);
+
+    let mut presets = row().spacing(8);
+    for minutes in QUICK_PRESETS {
+        presets = presets.push(
+            button::text(fl!("timer-preset-minutes", minutes = minutes))
+                .on_press(TimerMessage::Preset(minutes)),
+        );
+    }
+
+    let valid_input = parse_duration(&timer_state.input).is_some();
+    let mut content = column()
+        .push(list)
+        .push(entry)
+        .push(presets)
+        .push(
+            button::suggested(fl!("timer-start"))
+                .on_press_maybe(valid_input.then_some(TimerMessage::Add)),
+        )
+        .spacing(12);
+
+    if !valid_input {
+        content = content.push(text::caption(fl!("timer-invalid-duration")));
+    }
+
+    content.into()
+}
+
+// ============================================================================
+// Public View Function
+// ============================================================================
+
+/// Alarm data rendered by the Alarms mode
+pub struct AlarmsView<'a> {
+    pub locale: &'a Locale,
+    pub form: &'a AlarmForm,
+    pub alarms: &'a [Alarm],
+    pub schedule: &'a AlarmSchedule,
+    pub first_day_of_week: u8,
+}
+
+/// Renders the Timer tab: header, mode selector and the active mode's content
+pub fn view_timer<'a>(
+    timer_state: &'a TimerState,
+    alarms: AlarmsView<'a>,
+    settings: &PomodoroSettings,
+    now: DateTime<Utc>,
+) -> Element<'a, TimerMessage> {
     let Spacing {
         space_xxs, space_s, ..
     } = cosmic::theme::active().cosmic().spacing;
@@ -22,9 +702,46 @@ pub fn view_timer<'a>() -> Element<'a, Message> {
         .size(24) // Smaller to match calendar header height
         .into();
 
-    let features_icon: cosmic::widget::Icon = icon::from_name(icons::ui::STARRED).size(16).into();
+    let (status, content) = match timer_state.mode {
+        TimerMode::Countdown => (
+            fl!(
+                "timer-status-count",
+                running = timer_state
+                    .timers
+                    .iter()
+                    .filter(|timer| timer.countdown.is_running())
+                    .count(),
+                total = timer_state.timers.len()
+            ),
+            view_countdowns(timer_state, now),
+        ),
+        TimerMode::Pomodoro => (
+            status_label(timer_state.pomodoro.countdown().status()),
+            crate::pomodoro::view_pomodoro(&timer_state.pomodoro, settings, now)
+                .map(TimerMessage::Pomodoro),
+        ),
+        TimerMode::Stopwatch => (
+            stopwatch_status_label(&timer_state.stopwatch),
+            crate::stopwatch::view_stopwatch(&timer_state.stopwatch, Instant::now())
+                .map(TimerMessage::Stopwatch),
+        ),
+        TimerMode::Alarms => (
+            fl!(
+                "alarm-status",
+                count = alarms.alarms.iter().filter(|alarm| alarm.enabled).count()
+            ),
+            crate::alarm::view_alarms(
+                alarms.locale,
+                alarms.form,
+                alarms.alarms,
+                alarms.schedule,
+                alarms.first_day_of_week,
+            )
+            .map(TimerMessage::Alarm),
+        ),
+    };
 
-    // Match calendar structure: header + content (2 elements)
+    // Match calendar structure: header + content
     column()
         // Header (like calendar: icon + 2 text lines)
         .push(
@@ -34,40 +751,159 @@ pub fn view_timer<'a>() -> Element<'a, Message> {
                 .push(
                     column()
                         .push(text(fl!("timer")).size(18)) // Match calendar date size
-                        .push(text::body(fl!("timer-subtitle"))), // Match calendar day_of_week
+                        .push(text::body(status)), // Match calendar day_of_week
                 )
                 .align_y(Alignment::Center)
                 .padding([12, 20]), // Match calendar HEADER_PADDING
         )
         // Standard separator
         .push(padded_control(divider::horizontal::default()).padding([space_xxs, space_s]))
-        // Content (like calendar grid)
         .push(
             container(
                 column()
-                    .push(text::body(fl!("timer-placeholder-message")))
-                    .push(Space::with_height(Length::Fixed(12.0)))
-                    .push(
-                        row()
-                            .push(features_icon)
-                            .push(Space::with_width(Length::Fixed(8.0)))
-                            .push(text::heading(fl!("timer-placeholder-features")))
-                            .align_y(Alignment::Center),
-                    )
-                    .push(Space::with_height(Length::Fixed(8.0)))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("timer-feature-countdown"),
-                    ))
-                    .push(text::body("• ".to_string() + &fl!("timer-feature-presets")))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("timer-feature-notifications"),
-                    ))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("timer-feature-persistent"),
-                    ))
-                    .spacing(4),
+                    .push(mode_selector(timer_state.mode))
+                    .push(content)
+                    .spacing(12),
             )
-            .padding([0, 20]), // Match header horizontal padding for alignment
+            .padding([0, 20]), // Match header horizontal padding
         )
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_duration_forms() {
+        assert_eq!(parse_duration("25"), Some(TimeDelta::minutes(25)));
+        assert_eq!(parse_duration(" 5:30 "), Some(TimeDelta::seconds(330)));
+        assert_eq!(parse_duration("1:02:03"), Some(TimeDelta::seconds(3723)));
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("5:75"), None);
+        assert_eq!(parse_duration("abc"), None);
+        assert_eq!(parse_duration("-3"), None);
+    }
+
+    #[test]
+    fn test_session_saved_on_control_messages_only() {
+        assert!(TimerMessage::Add.changes_session());
+        assert!(TimerMessage::Snooze(1).changes_session());
+        assert!(TimerMessage::Pomodoro(PomodoroMessage::Skip).changes_session());
+        assert!(!TimerMessage::DurationInput("5".to_string()).changes_session());
+        assert!(!TimerMessage::LabelInput("Tea".to_string()).changes_session());
+        assert!(!TimerMessage::Stopwatch(StopwatchMessage::CopyLaps).changes_session());
+    }
+
+    #[test]
+    fn test_only_control_messages_stop_sounds() {
+        assert!(TimerMessage::Pause(1).stops_sound());
+        assert!(TimerMessage::Stopwatch(StopwatchMessage::Start).stops_sound());
+        assert!(!TimerMessage::DurationInput("5".to_string()).stops_sound());
+        assert!(!TimerMessage::LabelInput("Tea".to_string()).stops_sound());
+        assert!(!TimerMessage::SelectMode(TimerMode::Stopwatch).stops_sound());
+        assert!(!TimerMessage::Stopwatch(StopwatchMessage::CopyLaps).stops_sound());
+    }
+
+    #[test]
+    fn test_format_duration_rounds_up() {
+        assert_eq!(format_duration(TimeDelta::seconds(330)), "05:30");
+        assert_eq!(format_duration(TimeDelta::milliseconds(400)), "00:01");
+        assert_eq!(format_duration(TimeDelta::seconds(3723)), "1:02:03");
+        assert_eq!(format_duration(TimeDelta::zero()), "00:00");
+    }
+
+    #[test]
+    fn test_countdown_pause_resume_and_finish() {
+        let mut countdown = Countdown::new(TimeDelta::seconds(60));
+        countdown.start(TimeDelta::seconds(60), at(0));
+        assert_eq!(countdown.remaining(at(20)), TimeDelta::seconds(40));
+
+        countdown.pause(at(20));
+        assert_eq!(countdown.remaining(at(500)), TimeDelta::seconds(40));
+
+        countdown.resume(at(500));
+        assert!(!countdown.tick(at(539)));
+        assert!(countdown.tick(at(540)));
+        assert_eq!(countdown.status(), CountdownStatus::Finished);
+        assert!(!countdown.tick(at(541)));
+    }
+
+    #[test]
+    fn test_snooze_keeps_duration_for_restart() {
+        let mut countdown = Countdown::new(TimeDelta::minutes(20));
+        countdown.start(TimeDelta::minutes(20), at(0));
+        assert!(countdown.tick(at(1200)));
+
+        countdown.snooze(TimeDelta::minutes(SNOOZE_MINUTES), at(1200));
+        assert_eq!(countdown.remaining(at(1200)), TimeDelta::minutes(5));
+        countdown.restart(at(1500));
+        assert_eq!(countdown.remaining(at(1500)), TimeDelta::minutes(20));
+    }
+
+    #[test]
+    fn test_panel_prefers_running_countdown() {
+        let settings = PomodoroSettings::from_config(&Default::default());
+        let mut state = TimerState::default();
+        assert_eq!(state.panel_countdown(at(0)), None);
+
+        state.update(
+            TimerMessage::DurationInput("1:40".to_string()),
+            &settings,
+            at(0),
+        );
+        state.update(TimerMessage::Add, &settings, at(0));
+        state.update(TimerMessage::Pause(1), &settings, at(10));
+        state.pomodoro.start(&settings, at(0));
+
+        let shown = state.panel_countdown(at(60)).unwrap();
+        assert!(!shown.paused);
+        assert_eq!(shown.remaining, settings.work - TimeDelta::seconds(60));
+
+        state.pomodoro.reset();
+        let shown = state.panel_countdown(at(60)).unwrap();
+        assert!(shown.paused);
+        assert_eq!(shown.progress, 0.1);
+    }
+
+    #[test]
+    fn test_concurrent_timers_finish_independently() {
+        let settings = PomodoroSettings::from_config(&Default::default());
+        let mut state = TimerState::default();
+        state.update(
+            TimerMessage::LabelInput("Tea".to_string()),
+            &settings,
+            at(0),
+        );
+        state.update(TimerMessage::Preset(3), &settings, at(0));
+        state.update(TimerMessage::Preset(1), &settings, at(30));
+        assert!(state.needs_per_second_ticks());
+
+        assert_eq!(
+            state.tick(&settings, at(90)),
+            [TimerEvent::CountdownFinished {
+                id: 2,
+                label: fl!("timer-default-label", number = 2),
+            }]
+        );
+        assert!(state.needs_per_second_ticks());
+
+        state.update(TimerMessage::Remove(2), &settings, at(91));
+        assert_eq!(
+            state.tick(&settings, at(180)),
+            [TimerEvent::CountdownFinished {
+                id: 1,
+                label: "Tea".to_string(),
+            }]
+        );
+        assert!(!state.needs_per_second_ticks());
+
+        // The removed timer's ID is not handed out again
+        state.update(TimerMessage::Preset(1), &settings, at(181));
+        assert_eq!(state.timers.last().map(|timer| timer.id), Some(3));
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Weather units
+//!
+//! Providers report metric values (°C, km/h, hPa, mm); this module converts
+//! them to the units picked in the config and formats them for display. Each
+//! quantity has its own unit, so mixes such as °C with mph (as in the UK) are
+//! possible. Units left unset in the config follow the region of the system
+//! locale (e.g. en-US → imperial).
+
+use icu::locale::Locale;
+use serde::{Deserialize, Serialize};
+
+use crate::config::TimeAppletConfig;
+
+/// Regions where weather is customarily given in Fahrenheit and miles
+const IMPERIAL_REGIONS: [&str; 8] = ["US", "BS", "BZ", "KY", "LR", "PW", "FM", "MH"];
+
+/// Regions that use Celsius and millimetres, but miles per hour for wind
+const MPH_REGIONS: [&str; 1] = ["GB"];
+
+/// km/h per mile per hour
+const KMH_PER_MPH: f64 = 1.609_344;
+
+/// km/h per knot
+const KMH_PER_KNOT: f64 = 1.852;
+
+/// Hectopascals per inch of mercury
+const HPA_PER_INHG: f64 = 33.863_886_666;
+
+/// Millimetres per inch
+const MM_PER_INCH: f64 = 25.4;
+
+// ============================================================================
+// Units
+// ============================================================================
+
+/// Unit of temperatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl TemperatureUnit {
+    /// Convert from degrees Celsius
+    pub fn convert(self, celsius: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+            TemperatureUnit::Kelvin => celsius + 273.15,
+        }
+    }
+
+    /// Temperature in degrees Celsius, rounded to whole units ("21°C", "294 K")
+    pub fn format(self, celsius: f64) -> String {
+        let value = self.convert(celsius);
+        match self {
+            TemperatureUnit::Celsius => format!("{:.0}°C", value),
+            TemperatureUnit::Fahrenheit => format!("{:.0}°F", value),
+            TemperatureUnit::Kelvin => format!("{:.0} K", value),
+        }
+    }
+}
+
+/// Unit of wind speeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum WindSpeedUnit {
+    #[default]
+    KilometersPerHour,
+    MetersPerSecond,
+    MilesPerHour,
+    Knots,
+}
+
+impl WindSpeedUnit {
+    /// Convert from kilometres per hour
+    pub fn convert(self, kmh: f64) -> f64 {
+        match self {
+            WindSpeedUnit::KilometersPerHour => kmh,
+            WindSpeedUnit::MetersPerSecond => kmh / 3.6,
+            WindSpeedUnit::MilesPerHour => kmh / KMH_PER_MPH,
+            WindSpeedUnit::Knots => kmh / KMH_PER_KNOT,
+        }
+    }
+
+    /// Speed in km/h with its unit ("12 km/h", "3.4 m/s")
+    pub fn format(self, kmh: f64) -> String {
+        let value = self.convert(kmh);
+        match self {
+            WindSpeedUnit::KilometersPerHour => format!("{:.0} km/h", value),
+            // Typical winds are a few m/s: keep a decimal
+            WindSpeedUnit::MetersPerSecond => format!("{:.1} m/s", value),
+            WindSpeedUnit::MilesPerHour => format!("{:.0} mph", value),
+            WindSpeedUnit::Knots => format!("{:.0} kn", value),
+        }
+    }
+}
+
+/// Unit of atmospheric pressure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PressureUnit {
+    #[default]
+    Hectopascals,
+    InchesOfMercury,
+}
+
+impl PressureUnit {
+    /// Convert from hectopascals
+    pub fn convert(self, hpa: f64) -> f64 {
+        match self {
+            PressureUnit::Hectopascals => hpa,
+            PressureUnit::InchesOfMercury => hpa / HPA_PER_INHG,
+        }
+    }
+
+    /// Pressure in hPa with its unit ("1013 hPa", "29.92 inHg")
+    pub fn format(self, hpa: f64) -> String {
+        let value = self.convert(hpa);
+        match self {
+            PressureUnit::Hectopascals => format!("{:.0} hPa", value),
+            PressureUnit::InchesOfMercury => format!("{:.2} inHg", value),
+        }
+    }
+}
+
+/// Unit of precipitation amounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PrecipitationUnit {
+    #[default]
+    Millimeters,
+    Inches,
+}
+
+impl PrecipitationUnit {
+    /// Convert from millimetres
+    pub fn convert(self, mm: f64) -> f64 {
+        match self {
+            PrecipitationUnit::Millimeters => mm,
+            PrecipitationUnit::Inches => mm / MM_PER_INCH,
+        }
+    }
+
+    /// Amount in mm with its unit ("4.2 mm", "0.17 in")
+    pub fn format(self, mm: f64) -> String {
+        let value = self.convert(mm);
+        match self {
+            PrecipitationUnit::Millimeters => format!("{:.1} mm", value),
+            PrecipitationUnit::Inches => format!("{:.2} in", value),
+        }
+    }
+}
+
+// ============================================================================
+// Unit Selection
+// ============================================================================
+
+/// Units weather values are displayed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Units {
+    pub temperature: TemperatureUnit,
+    pub wind_speed: WindSpeedUnit,
+    pub pressure: PressureUnit,
+    pub precipitation: PrecipitationUnit,
+}
+
+impl Units {
+    /// °F, mph, inHg and inches
+    pub fn imperial() -> Self {
+        Self {
+            temperature: TemperatureUnit::Fahrenheit,
+            wind_speed: WindSpeedUnit::MilesPerHour,
+            pressure: PressureUnit::InchesOfMercury,
+            precipitation: PrecipitationUnit::Inches,
+        }
+    }
+
+    /// Customary units of a region (ISO 3166 code); metric when unknown
+    pub fn for_region(region: Option<&str>) -> Self {
+        match region {
+            Some(region) if IMPERIAL_REGIONS.contains(&region) => Self::imperial(),
+            Some(region) if MPH_REGIONS.contains(&region) => Self {
+                wind_speed: WindSpeedUnit::MilesPerHour,
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Customary units of the locale's region (e.g. en-US → imperial)
+    pub fn from_locale(locale: &Locale) -> Self {
+        Self::for_region(locale.id.region.as_ref().map(|region| region.as_str()))
+    }
+
+    /// Units picked in the config, the locale's defaults for those left unset
+    pub fn resolve(config: &TimeAppletConfig, locale: &Locale) -> Self {
+        let defaults = Self::from_locale(locale);
+        Self {
+            temperature: config
+                .weather_temperature_unit
+                .unwrap_or(defaults.temperature),
+            wind_speed: config
+                .weather_wind_speed_unit
+                .unwrap_or(defaults.wind_speed),
+            pressure: config.weather_pressure_unit.unwrap_or(defaults.pressure),
+            precipitation: config
+                .weather_precipitation_unit
+                .unwrap_or(defaults.precipitation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(tag: &str) -> Locale {
+        Locale::try_from_str(tag).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_close(TemperatureUnit::Fahrenheit.convert(0.0), 32.0);
+        assert_close(TemperatureUnit::Fahrenheit.convert(-40.0), -40.0);
+        assert_close(TemperatureUnit::Kelvin.convert(0.0), 273.15);
+
+        assert_close(WindSpeedUnit::MetersPerSecond.convert(36.0), 10.0);
+        assert_close(
+            WindSpeedUnit::MilesPerHour.convert(KMH_PER_MPH * 10.0),
+            10.0,
+        );
+        assert_close(WindSpeedUnit::Knots.convert(100.0), 54.0);
+
+        assert_close(PressureUnit::InchesOfMercury.convert(1013.25), 29.92);
+        assert_close(PrecipitationUnit::Inches.convert(25.4), 1.0);
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(TemperatureUnit::Celsius.format(21.4), "21°C");
+        assert_eq!(TemperatureUnit::Fahrenheit.format(21.4), "71°F");
+        assert_eq!(TemperatureUnit::Kelvin.format(21.4), "295 K");
+        assert_eq!(WindSpeedUnit::MetersPerSecond.format(12.0), "3.3 m/s");
+        assert_eq!(PressureUnit::InchesOfMercury.format(1013.25), "29.92 inHg");
+        assert_eq!(PrecipitationUnit::Millimeters.format(4.0), "4.0 mm");
+    }
+
+    #[test]
+    fn test_locale_defaults() {
+        assert_eq!(Units::from_locale(&locale("en-US")), Units::imperial());
+        assert_eq!(Units::from_locale(&locale("pt-BR")), Units::default());
+        assert_eq!(Units::from_locale(&locale("en")), Units::default());
+
+        let uk = Units::from_locale(&locale("en-GB"));
+        assert_eq!(uk.temperature, TemperatureUnit::Celsius);
+        assert_eq!(uk.wind_speed, WindSpeedUnit::MilesPerHour);
+    }
+
+    #[test]
+    fn test_config_overrides_locale() {
+        let config = TimeAppletConfig {
+            weather_temperature_unit: Some(TemperatureUnit::Celsius),
+            weather_wind_speed_unit: Some(WindSpeedUnit::Knots),
+            ..TimeAppletConfig::default()
+        };
+        let units = Units::resolve(&config, &locale("en-US"));
+        assert_eq!(units.temperature, TemperatureUnit::Celsius);
+        assert_eq!(units.wind_speed, WindSpeedUnit::Knots);
+        assert_eq!(units.pressure, PressureUnit::InchesOfMercury);
+        assert_eq!(units.precipitation, PrecipitationUnit::Inches);
+    }
+}
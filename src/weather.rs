@@ -1,19 +1,499 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+//! Weather module
+//!
+//! Weather tab state and view. Fetching is delegated to a `WeatherProvider`
+//! (see `weather_provider.rs`) by Window, which owns the async tasks; this
+//! module only decides when data is due and renders what came back.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
 use cosmic::{
     Element,
     applet::padded_control,
     cosmic_theme::Spacing,
     iced::{Alignment, Length},
-    widget::{Space, column, container, divider, icon, row, text},
+    widget::{Space, button, column, container, divider, icon, row, scrollable, text, text_input},
+};
+use icu::{
+    datetime::{
+        DateTimeFormatter, DateTimeFormatterPreferences, fieldsets, options::TimePrecision,
+    },
+    locale::Locale,
 };
 
+use crate::astronomy::{Moon, SunTimes, view_moon, view_sun_times};
+use crate::calendar::create_datetime;
 use crate::fl;
-use crate::{Message, Tab, icons};
+use crate::location::{self, Place};
+use crate::units::Units;
+use crate::weather_alerts::WeatherAlert;
+use crate::weather_provider::{
+    Coordinates, CurrentConditions, DailyForecast, HourlyForecast, WeatherCondition, WeatherError,
+    WeatherReport,
+};
+use crate::{Tab, icons};
+
+/// How often the weather report is refreshed
+const REFRESH_INTERVAL: TimeDelta = TimeDelta::minutes(30);
+
+/// First retry delay after a failed fetch, doubled on each further failure
+/// up to `REFRESH_INTERVAL`
+const RETRY_DELAY: TimeDelta = TimeDelta::minutes(1);
+
+/// Age after which a report is flagged as stale in the tab
+const STALE_AFTER: TimeDelta = TimeDelta::hours(2);
+
+/// Wind arrows by the direction the wind blows from (N, NE, E, ..., NW)
+const WIND_ARROWS: [&str; 8] = ["↓", "↙", "←", "↖", "↑", "↗", "→", "↘"];
+
+/// Hours shown in the hourly forecast
+const HOURLY_HOURS: usize = 24;
+
+/// Width of one hour in the hourly forecast strip
+const HOUR_COLUMN_WIDTH: f32 = 52.0;
+
+// ============================================================================
+// Weather State
+// ============================================================================
+
+/// State management for the Weather tab
+#[derive(Debug, Clone, Default)]
+pub struct WeatherState {
+    /// Last successfully fetched report (possibly restored from the cache)
+    pub report: Option<WeatherReport>,
+    /// When `report` was fetched
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Error of the last fetch, cleared by the next success
+    pub error: Option<WeatherError>,
+    /// A request is in flight
+    fetching: bool,
+    /// When the last request was started
+    last_attempt: Option<DateTime<Utc>>,
+    /// Consecutive failed requests (drives the retry backoff)
+    failures: u32,
+    /// City search input of the location picker
+    pub location_query: String,
+    /// Alerts of the last successful alerts feed request
+    pub alerts: Vec<WeatherAlert>,
+}
+
+impl WeatherState {
+    /// State with a cached report, refreshed once it is `REFRESH_INTERVAL` old
+    pub fn restore(report: WeatherReport, fetched_at: DateTime<Utc>) -> Self {
+        Self {
+            report: Some(report),
+            fetched_at: Some(fetched_at),
+            last_attempt: Some(fetched_at),
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the next request: the refresh interval, or the backoff
+    /// after failures (1, 2, 4, ... minutes)
+    fn next_delay(&self) -> TimeDelta {
+        match self.failures {
+            0 => REFRESH_INTERVAL,
+            failures => {
+                let factor = 1_i32 << (failures - 1).min(16);
+                (RETRY_DELAY * factor).min(REFRESH_INTERVAL)
+            }
+        }
+    }
+
+    /// Check if a new request should be started at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !self.fetching
+            && self
+                .last_attempt
+                .is_none_or(|attempt| now - attempt >= self.next_delay())
+    }
+
+    /// Make the next tick fetch right away (e.g. after resuming from sleep)
+    pub fn expire(&mut self) {
+        self.last_attempt = None;
+        self.failures = 0;
+    }
+
+    /// Check if the shown report is old enough to deserve a warning
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.fetched_at
+            .is_some_and(|fetched_at| now - fetched_at >= STALE_AFTER)
+    }
+
+    /// Report recent enough to be shown without a staleness warning
+    pub fn fresh_report(&self, now: DateTime<Utc>) -> Option<&WeatherReport> {
+        self.report.as_ref().filter(|_| !self.is_stale(now))
+    }
+
+    /// Alerts in effect at `now`, most severe first
+    pub fn active_alerts(&self, now: DateTime<Utc>) -> Vec<&WeatherAlert> {
+        let mut alerts: Vec<&WeatherAlert> = self
+            .alerts
+            .iter()
+            .filter(|alert| alert.is_active(now))
+            .collect();
+        alerts.sort_by_key(|alert| std::cmp::Reverse(alert.severity));
+        alerts
+    }
+
+    /// Check if a request is in flight
+    pub fn is_fetching(&self) -> bool {
+        self.fetching
+    }
+
+    /// Record that a request was started at `now`
+    pub fn begin_fetch(&mut self, now: DateTime<Utc>) {
+        self.fetching = true;
+        self.last_attempt = Some(now);
+    }
+
+    /// Update weather state based on message
+    pub fn update(&mut self, message: WeatherMessage) {
+        match message {
+            // Requests are started by Window, which owns the provider task
+            WeatherMessage::Refresh => {}
+            WeatherMessage::Fetched(_, Ok(report)) => {
+                self.fetching = false;
+                self.report = Some(report);
+                self.fetched_at = self.last_attempt;
+                self.error = None;
+                self.failures = 0;
+            }
+            WeatherMessage::Fetched(_, Err(err)) => {
+                // Keep showing the previous report: stale data beats no data
+                self.fetching = false;
+                self.error = Some(err);
+                self.failures = self.failures.saturating_add(1);
+            }
+            WeatherMessage::AlertsFetched(_, Ok(alerts)) => {
+                self.alerts = alerts;
+            }
+            // Known alerts stay until they expire: a failed request proves nothing
+            WeatherMessage::AlertsFetched(_, Err(_)) => {}
+            WeatherMessage::LocationQuery(query) => {
+                self.location_query = query;
+            }
+            // The location itself is saved to the config by Window
+            WeatherMessage::PickPlace(_) | WeatherMessage::UseTimezoneLocation => {
+                self.location_query.clear();
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Weather Messages
+// ============================================================================
+
+/// Messages for weather interactions
+#[derive(Debug, Clone)]
+pub enum WeatherMessage {
+    /// Fetch again now (refresh button)
+    Refresh,
+    /// Result of a provider request for a location (Window drops results of
+    /// a location that is no longer the current one)
+    Fetched(Coordinates, Result<WeatherReport, WeatherError>),
+    /// Result of an alerts feed request for a location
+    AlertsFetched(Coordinates, Result<Vec<WeatherAlert>, WeatherError>),
+    /// Location search input changed
+    LocationQuery(String),
+    /// Use a city from the search results as the weather location
+    PickPlace(Place),
+    /// Forget the configured location and follow the system timezone
+    UseTimezoneLocation,
+}
+
+// ============================================================================
+// View Helpers
+// ============================================================================
+
+/// Symbolic icon for a condition, with night variants where the theme has them
+pub fn condition_icon(condition: WeatherCondition, is_day: bool) -> &'static str {
+    match (condition, is_day) {
+        (WeatherCondition::Clear, true) => icons::weather::CLEAR,
+        (WeatherCondition::Clear, false) => icons::weather::CLEAR_NIGHT,
+        (WeatherCondition::PartlyCloudy, true) => icons::weather::FEW_CLOUDS,
+        (WeatherCondition::PartlyCloudy, false) => icons::weather::FEW_CLOUDS_NIGHT,
+        (WeatherCondition::Overcast, _) => icons::weather::OVERCAST,
+        (WeatherCondition::Fog, _) => icons::weather::FOG,
+        (WeatherCondition::Drizzle | WeatherCondition::Showers, _) => {
+            icons::weather::SHOWERS_SCATTERED
+        }
+        (WeatherCondition::Rain | WeatherCondition::FreezingRain, _) => icons::weather::SHOWERS,
+        (WeatherCondition::Snow, _) => icons::weather::SNOW,
+        (WeatherCondition::Thunderstorm, _) => icons::weather::STORM,
+        (WeatherCondition::Unknown, _) => Tab::Weather.icon_name(),
+    }
+}
+
+/// Translated name of a condition
+pub fn condition_label(condition: WeatherCondition) -> String {
+    match condition {
+        WeatherCondition::Clear => fl!("weather-clear"),
+        WeatherCondition::PartlyCloudy => fl!("weather-partly-cloudy"),
+        WeatherCondition::Overcast => fl!("weather-overcast"),
+        WeatherCondition::Fog => fl!("weather-fog"),
+        WeatherCondition::Drizzle => fl!("weather-drizzle"),
+        WeatherCondition::Rain => fl!("weather-rain"),
+        WeatherCondition::FreezingRain => fl!("weather-freezing-rain"),
+        WeatherCondition::Snow => fl!("weather-snow"),
+        WeatherCondition::Showers => fl!("weather-showers"),
+        WeatherCondition::Thunderstorm => fl!("weather-thunderstorm"),
+        WeatherCondition::Unknown => fl!("weather-unknown"),
+    }
+}
+
+/// Arrow pointing where wind coming from `direction` (degrees) blows to
+fn wind_arrow(direction: f64) -> &'static str {
+    let sector = (direction.rem_euclid(360.0) / 45.0).round() as usize % WIND_ARROWS.len();
+    WIND_ARROWS[sector]
+}
+
+/// Label / value line of the details list
+fn detail_row<'a, M: 'a>(label: String, value: String) -> Element<'a, M> {
+    row()
+        .push(text::body(label).width(Length::Fill))
+        .push(text::body(value))
+        .into()
+}
+
+/// Current conditions: temperature, feels-like, wind, humidity and pressure
+fn view_current<'a>(current: &CurrentConditions, units: Units) -> Element<'a, WeatherMessage> {
+    let condition_icon: cosmic::widget::Icon =
+        icon::from_name(condition_icon(current.condition, current.is_day))
+            .size(48)
+            .into();
+
+    let mut details = column()
+        .push(
+            row()
+                .push(condition_icon)
+                .push(Space::with_width(Length::Fixed(12.0)))
+                .push(text::title1(units.temperature.format(current.temperature)))
+                .align_y(Alignment::Center),
+        )
+        .push(detail_row(
+            fl!("weather-feels-like"),
+            units.temperature.format(current.feels_like),
+        ))
+        .push(detail_row(
+            fl!("weather-wind"),
+            format!(
+                "{} {}",
+                wind_arrow(current.wind_direction),
+                units.wind_speed.format(current.wind_speed)
+            ),
+        ))
+        .push(detail_row(
+            fl!("weather-humidity"),
+            format!("{:.0}%", current.humidity),
+        ))
+        .spacing(8);
+    if let Some(pressure) = current.pressure {
+        details = details.push(detail_row(
+            fl!("weather-pressure"),
+            units.pressure.format(pressure),
+        ));
+    }
+    details.into()
+}
+
+/// "Updated N minutes ago" line for a report fetched at `fetched_at`
+fn updated_label(fetched_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - fetched_at).num_minutes().max(0);
+    if minutes < 60 {
+        fl!("weather-updated-minutes", minutes = minutes)
+    } else {
+        fl!("weather-updated-hours", hours = minutes / 60)
+    }
+}
+
+/// Chance of precipitation, or an empty line when unknown (keeps columns aligned)
+fn format_probability(probability: Option<f64>) -> String {
+    probability.map_or_else(String::new, |probability| format!("{:.0}%", probability))
+}
+
+/// Precipitation amount, or an empty line when none or unknown
+fn format_precipitation(amount: Option<f64>, units: Units) -> String {
+    amount
+        .filter(|amount| *amount > 0.0)
+        .map_or_else(String::new, |amount| units.precipitation.format(amount))
+}
+
+/// Daily forecast strip: one column per day with icon, high, low, rain
+/// chance and amount
+fn view_daily<'a>(
+    locale: &Locale,
+    daily: &[DailyForecast],
+    today: NaiveDate,
+    units: Units,
+) -> Element<'a, WeatherMessage> {
+    // Create formatter once for this render (following cosmic-applet-time pattern)
+    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    let weekday_formatter = DateTimeFormatter::try_new(prefs, fieldsets::E::short()).unwrap();
 
-/// Weather tab placeholder view
-pub fn view_weather<'a>() -> Element<'a, Message> {
+    let mut days = row().spacing(4);
+    for day in daily {
+        let label = if day.date == today {
+            fl!("weather-today")
+        } else {
+            let datetime = create_datetime(&day.date, &NaiveTime::MIN);
+            weekday_formatter.format(&datetime).to_string()
+        };
+        let day_icon: cosmic::widget::Icon = icon::from_name(condition_icon(day.condition, true))
+            .size(24)
+            .into();
+
+        days = days.push(
+            column()
+                .push(text::caption(label))
+                .push(day_icon)
+                .push(text::body(units.temperature.format(day.temperature_max)))
+                .push(text::caption(units.temperature.format(day.temperature_min)))
+                .push(text::caption(format_probability(
+                    day.precipitation_probability,
+                )))
+                .push(text::caption(format_precipitation(
+                    day.precipitation_sum,
+                    units,
+                )))
+                .align_x(Alignment::Center)
+                .spacing(2)
+                .width(Length::Fill),
+        );
+    }
+    days.into()
+}
+
+/// Hourly forecast: horizontally scrolling strip of the next hours (from the
+/// current one)
+fn view_hourly<'a>(
+    locale: &Locale,
+    hourly: &[HourlyForecast],
+    units: Units,
+) -> Element<'a, WeatherMessage> {
+    let prefs = DateTimeFormatterPreferences::from(locale.clone());
+    let hour_formatter = DateTimeFormatter::try_new(
+        prefs,
+        fieldsets::T::medium().with_time_precision(TimePrecision::Minute),
+    )
+    .unwrap();
+
+    let mut hours = row().spacing(4);
+    for hour in hourly.iter().take(HOURLY_HOURS) {
+        let datetime = create_datetime(&hour.time, &hour.time);
+        let hour_icon: cosmic::widget::Icon =
+            icon::from_name(condition_icon(hour.condition, hour.is_day))
+                .size(20)
+                .into();
+
+        hours = hours.push(
+            column()
+                .push(text::caption(hour_formatter.format(&datetime).to_string()))
+                .push(hour_icon)
+                .push(text::body(units.temperature.format(hour.temperature)))
+                .push(text::caption(format_probability(
+                    hour.precipitation_probability,
+                )))
+                .align_x(Alignment::Center)
+                .spacing(2)
+                .width(Length::Fixed(HOUR_COLUMN_WIDTH)),
+        );
+    }
+    scrollable::horizontal(hours).into()
+}
+
+/// Banner with the alerts in effect, one card per alert
+fn view_alerts<'a>(alerts: &[&WeatherAlert]) -> Element<'a, WeatherMessage> {
+    let mut banner = column().spacing(4);
+    for alert in alerts {
+        let alert_icon: cosmic::widget::Icon =
+            icon::from_name(icons::weather::ALERT).size(20).into();
+        banner = banner.push(
+            container(
+                row()
+                    .push(alert_icon)
+                    .push(
+                        column()
+                            .push(text::heading(alert.event.clone()))
+                            .push(text::caption(alert.title.clone()))
+                            .spacing(2),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8),
+            )
+            .padding([6, 10])
+            .width(Length::Fill)
+            .class(cosmic::theme::Container::Card),
+        );
+    }
+    banner.into()
+}
+
+/// Location line, city search and matching cities
+///
+/// `automatic` marks a location derived from the system timezone, which a
+/// configured location overrides until reset.
+fn view_location<'a>(
+    weather_state: &'a WeatherState,
+    location: Option<Place>,
+    automatic: bool,
+) -> Element<'a, WeatherMessage> {
+    let mut current = row().align_y(Alignment::Center).spacing(8);
+    if let Some(place) = location {
+        current = current.push(text::body(place.name).width(Length::Fill));
+        current = if automatic {
+            current.push(text::caption(fl!("weather-location-from-timezone")))
+        } else {
+            current.push(
+                button::text(fl!("weather-location-use-timezone"))
+                    .on_press(WeatherMessage::UseTimezoneLocation),
+            )
+        };
+    }
+
+    let mut results = column().spacing(2);
+    for place in location::search(&weather_state.location_query) {
+        results = results.push(
+            button::text(place.name.clone())
+                .on_press(WeatherMessage::PickPlace(place))
+                .width(Length::Fill),
+        );
+    }
+
+    column()
+        .push(current)
+        .push(
+            text_input(
+                fl!("weather-location-placeholder"),
+                weather_state.location_query.as_str(),
+            )
+            .on_input(WeatherMessage::LocationQuery),
+        )
+        .push(results)
+        .spacing(8)
+        .into()
+}
+
+// ============================================================================
+// Public View Function
+// ============================================================================
+
+/// Renders the Weather tab: header, current conditions, forecasts and refresh control
+///
+/// Without a `location` (none configured and no city known for the system
+/// timezone) the tab explains how to pick one instead of showing an endless
+/// spinner. `automatic` tells whether `location` comes from the timezone.
+pub fn view_weather<'a>(
+    locale: &'a Locale,
+    weather_state: &'a WeatherState,
+    location: Option<Place>,
+    automatic: bool,
+    units: Units,
+    sun: Option<&SunTimes>,
+    now: DateTime<Utc>,
+) -> Element<'a, WeatherMessage> {
     let Spacing {
         space_xxs, space_s, ..
     } = cosmic::theme::active().cosmic().spacing;
@@ -22,9 +502,84 @@ pub fn view_weather<'a>() -> Element<'a, Message> {
         .size(24) // Smaller to match calendar header height
         .into();
 
-    let features_icon: cosmic::widget::Icon = icon::from_name(icons::ui::STARRED).size(16).into();
+    let status = if location.is_none() {
+        fl!("weather-no-location")
+    } else if let Some(report) = &weather_state.report {
+        condition_label(report.current.condition)
+    } else if weather_state.error.is_some() {
+        fl!("weather-unavailable")
+    } else {
+        fl!("weather-loading")
+    };
+
+    let mut content = column().spacing(12);
+    let alerts = weather_state.active_alerts(now);
+    if location.is_some() && !alerts.is_empty() {
+        content = content.push(view_alerts(&alerts));
+    }
+    if location.is_none() {
+        content = content.push(text::body(fl!("weather-no-location-hint")));
+    } else {
+        if let Some(report) = &weather_state.report {
+            content = content.push(view_current(&report.current, units));
+            // A cached report may be hours or days old: skip what has passed
+            let local_now = report.local_time(now);
+            let hourly = report.hours_from(local_now);
+            if !hourly.is_empty() {
+                content = content
+                    .push(text::heading(fl!("weather-hourly")))
+                    .push(view_hourly(locale, hourly, units));
+            }
+            let daily = report.days_from(local_now.date());
+            if !daily.is_empty() {
+                content = content
+                    .push(text::heading(fl!("weather-daily")))
+                    .push(view_daily(locale, daily, local_now.date(), units));
+            }
+        }
+        // Computed locally: shown even while offline
+        if let Some(sun) = sun {
+            content = content
+                .push(text::heading(fl!("weather-sun")))
+                .push(view_sun_times(locale, sun));
+        }
+        content = content
+            .push(text::heading(fl!("weather-moon")))
+            .push(view_moon(&Moon::at(now)));
+        if let Some(err) = &weather_state.error {
+            content = content.push(text::caption(fl!("weather-error", error = err.to_string())));
+        }
 
-    // Match calendar structure: header + content (2 elements)
+        let mut footer = row().align_y(Alignment::Center).spacing(8);
+        if let Some(fetched_at) = weather_state.fetched_at {
+            if weather_state.is_stale(now) {
+                let stale_icon: cosmic::widget::Icon =
+                    icon::from_name(icons::weather::STALE).size(16).into();
+                footer = footer.push(
+                    container(
+                        row()
+                            .push(stale_icon)
+                            .push(text::caption(fl!("weather-stale")))
+                            .align_y(Alignment::Center)
+                            .spacing(4),
+                    )
+                    .padding([2, 8])
+                    .class(cosmic::theme::Container::Card),
+                );
+            }
+            footer = footer.push(text::caption(updated_label(fetched_at, now)));
+        }
+        footer = footer.push(Space::with_width(Length::Fill)).push(
+            button::standard(fl!("weather-refresh"))
+                .on_press_maybe((!weather_state.is_fetching()).then_some(WeatherMessage::Refresh)),
+        );
+        content = content.push(footer);
+    }
+    content = content
+        .push(divider::horizontal::default())
+        .push(view_location(weather_state, location, automatic));
+
+    // Match calendar structure: header + content
     column()
         // Header (like calendar: icon + 2 text lines)
         .push(
@@ -34,7 +589,7 @@ pub fn view_weather<'a>() -> Element<'a, Message> {
                 .push(
                     column()
                         .push(text(fl!("weather")).size(18)) // Match calendar date size
-                        .push(text::body(fl!("weather-subtitle"))), // Match calendar day_of_week
+                        .push(text::body(status)), // Match calendar day_of_week
                 )
                 .align_y(Alignment::Center)
                 .padding([12, 20]), // Match calendar HEADER_PADDING
@@ -42,31 +597,137 @@ pub fn view_weather<'a>() -> Element<'a, Message> {
         // Standard separator
         .push(padded_control(divider::horizontal::default()).padding([space_xxs, space_s]))
         // Content (like calendar grid)
-        .push(
-            container(
-                column()
-                    .push(text::body(fl!("weather-placeholder-message")))
-                    .push(Space::with_height(Length::Fixed(12.0)))
-                    .push(
-                        row()
-                            .push(features_icon)
-                            .push(Space::with_width(Length::Fixed(8.0)))
-                            .push(text::heading(fl!("weather-placeholder-features")))
-                            .align_y(Alignment::Center),
-                    )
-                    .push(Space::with_height(Length::Fixed(8.0)))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("weather-feature-current"),
-                    ))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("weather-feature-temperature"),
-                    ))
-                    .push(text::body(
-                        "• ".to_string() + &fl!("weather-feature-forecast"),
-                    ))
-                    .spacing(4),
-            )
-            .padding([0, 20]), // Match header horizontal padding for alignment
-        )
+        .push(container(content).padding([0, 20])) // Match header horizontal padding for alignment
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_alerts::AlertSeverity;
+
+    const HERE: Coordinates = Coordinates {
+        latitude: 52.52,
+        longitude: 13.41,
+    };
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + minutes * 60, 0).unwrap()
+    }
+
+    #[test]
+    fn test_refresh_is_due_after_interval() {
+        let mut state = WeatherState::default();
+        assert!(state.is_due(at(0)));
+
+        state.begin_fetch(at(0));
+        assert!(!state.is_due(at(45)), "request still in flight");
+
+        state.update(WeatherMessage::Fetched(
+            HERE,
+            Err(WeatherError::Status(500)),
+        ));
+        assert!(state.is_due(at(1)), "first retry after a minute");
+
+        state.begin_fetch(at(1));
+        state.update(WeatherMessage::Fetched(
+            HERE,
+            Err(WeatherError::Status(500)),
+        ));
+        assert!(!state.is_due(at(2)));
+        assert!(state.is_due(at(3)), "backoff doubles");
+
+        for minute in 3..12 {
+            state.begin_fetch(at(minute));
+            state.update(WeatherMessage::Fetched(
+                HERE,
+                Err(WeatherError::Status(500)),
+            ));
+        }
+        assert!(!state.is_due(at(40)));
+        assert!(
+            state.is_due(at(41)),
+            "backoff capped at the refresh interval"
+        );
+    }
+
+    #[test]
+    fn test_cached_report_goes_stale() {
+        let report = WeatherReport {
+            current: CurrentConditions {
+                temperature: 20.0,
+                feels_like: 20.0,
+                condition: WeatherCondition::Clear,
+                wind_speed: 0.0,
+                wind_direction: 0.0,
+                humidity: 50.0,
+                pressure: None,
+                is_day: true,
+            },
+            daily: Vec::new(),
+            hourly: Vec::new(),
+            utc_offset_seconds: None,
+        };
+        let mut state = WeatherState::restore(report, at(0));
+        assert!(!state.is_due(at(10)), "recent cache is served as is");
+        assert!(state.is_due(at(30)));
+
+        // Offline: the cached report is kept and flagged
+        state.begin_fetch(at(30));
+        state.update(WeatherMessage::Fetched(
+            HERE,
+            Err(WeatherError::Request("offline".to_string())),
+        ));
+        assert!(state.report.is_some());
+        assert!(!state.is_stale(at(119)));
+        assert!(state.is_stale(at(120)));
+        assert!(state.fresh_report(at(119)).is_some());
+        assert!(
+            state.fresh_report(at(120)).is_none(),
+            "the panel drops outdated weather"
+        );
+
+        state.expire();
+        assert!(state.is_due(at(31)), "wake from sleep fetches right away");
+    }
+
+    #[test]
+    fn test_alerts_survive_failed_requests() {
+        let alert = |id: &str, severity, expires| WeatherAlert {
+            id: id.to_string(),
+            title: id.to_string(),
+            event: id.to_string(),
+            severity,
+            summary: String::new(),
+            expires,
+        };
+        let mut state = WeatherState::default();
+        state.update(WeatherMessage::AlertsFetched(
+            HERE,
+            Ok(vec![
+                alert("frost", AlertSeverity::Minor, Some(at(60))),
+                alert("storm", AlertSeverity::Severe, None),
+            ]),
+        ));
+        state.update(WeatherMessage::AlertsFetched(
+            HERE,
+            Err(WeatherError::Status(500)),
+        ));
+
+        let active: Vec<&str> = state
+            .active_alerts(at(0))
+            .iter()
+            .map(|alert| alert.id.as_str())
+            .collect();
+        assert_eq!(active, ["storm", "frost"], "most severe first");
+        assert_eq!(state.active_alerts(at(60)).len(), 1, "expired alert hidden");
+    }
+
+    #[test]
+    fn test_wind_arrow_sectors() {
+        assert_eq!(wind_arrow(0.0), "↓");
+        assert_eq!(wind_arrow(350.0), "↓");
+        assert_eq!(wind_arrow(135.0), "↖");
+        assert_eq!(wind_arrow(-90.0), "→");
+    }
+}
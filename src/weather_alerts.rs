@@ -0,0 +1,378 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Severe weather alerts
+//!
+//! Alerts (storm, heat, frost warnings, ...) come from the feed configured in
+//! `weather_alerts_url`, either an Atom feed with CAP fields (as published by
+//! the US National Weather Service or MeteoAlarm) or a single CAP message.
+//! The URL may contain `{latitude}` and `{longitude}` placeholders for feeds
+//! filtered by point.
+//!
+//! Each alert is notified once: the IDs already notified are kept in
+//! `$XDG_STATE_HOME/cosmic-applet-timeplus` until the alert expires, so a
+//! restart does not announce the same warning again.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::weather_provider::{Coordinates, WeatherError};
+
+/// Give up on a request after this long (the panel must never hang on it)
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// File name of the notified alert IDs inside the state directory
+const NOTIFIED_FILE: &str = "weather_alerts.json";
+
+/// How long the ID of an alert without an expiry time is remembered
+const REMEMBER_WITHOUT_EXPIRY: TimeDelta = TimeDelta::days(7);
+
+// ============================================================================
+// Alerts
+// ============================================================================
+
+/// CAP severity of an alert, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Unknown,
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl AlertSeverity {
+    /// Parse the CAP `severity` value
+    fn from_cap(value: &str) -> Self {
+        match value.trim() {
+            "Minor" => AlertSeverity::Minor,
+            "Moderate" => AlertSeverity::Moderate,
+            "Severe" => AlertSeverity::Severe,
+            "Extreme" => AlertSeverity::Extreme,
+            _ => AlertSeverity::Unknown,
+        }
+    }
+}
+
+/// An active weather warning
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherAlert {
+    /// Identifier, stable across feed updates (used for deduplication)
+    pub id: String,
+    /// Headline ("Heat Advisory issued July 10 at 3:02PM CDT ...")
+    pub title: String,
+    /// Kind of event ("Heat Advisory"), the title when the feed has none
+    pub event: String,
+    pub severity: AlertSeverity,
+    /// Longer description, possibly empty
+    pub summary: String,
+    /// End of validity, if given
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl WeatherAlert {
+    /// Check if the alert is still in effect at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+    }
+}
+
+// ============================================================================
+// Feed Parsing
+// ============================================================================
+
+/// Trimmed text of the first child element named `name` (namespace ignored)
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+        .and_then(|child| child.text())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn parse_time(value: Option<String>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&value?)
+        .ok()
+        .map(|time| time.to_utc())
+}
+
+/// Alert from an Atom `<entry>` carrying CAP fields
+fn alert_from_entry(entry: roxmltree::Node) -> Option<WeatherAlert> {
+    let id = child_text(entry, "id").or_else(|| child_text(entry, "identifier"))?;
+    let title = child_text(entry, "title").or_else(|| child_text(entry, "headline"))?;
+    Some(WeatherAlert {
+        id,
+        event: child_text(entry, "event").unwrap_or_else(|| title.clone()),
+        title,
+        severity: AlertSeverity::from_cap(&child_text(entry, "severity").unwrap_or_default()),
+        summary: child_text(entry, "summary")
+            .or_else(|| child_text(entry, "description"))
+            .unwrap_or_default(),
+        expires: parse_time(child_text(entry, "expires")),
+    })
+}
+
+/// Alert from a CAP `<alert>` message (its first `<info>` block)
+fn alert_from_cap(alert: roxmltree::Node) -> Option<WeatherAlert> {
+    let id = child_text(alert, "identifier")?;
+    let info = alert
+        .children()
+        .find(|child| child.is_element() && child.tag_name().name() == "info")?;
+    let event = child_text(info, "event")?;
+    Some(WeatherAlert {
+        id,
+        title: child_text(info, "headline").unwrap_or_else(|| event.clone()),
+        event,
+        severity: AlertSeverity::from_cap(&child_text(info, "severity").unwrap_or_default()),
+        summary: child_text(info, "description").unwrap_or_default(),
+        expires: parse_time(child_text(info, "expires")),
+    })
+}
+
+/// Parse an Atom feed of CAP entries or a single CAP message
+///
+/// Entries without an ID or title are skipped. Expired alerts are kept;
+/// callers filter with `WeatherAlert::is_active`.
+pub fn parse_alerts(xml: &str) -> Result<Vec<WeatherAlert>, WeatherError> {
+    let document =
+        roxmltree::Document::parse(xml).map_err(|err| WeatherError::Parse(err.to_string()))?;
+    let root = document.root_element();
+    match root.tag_name().name() {
+        "feed" => Ok(root
+            .children()
+            .filter(|child| child.is_element() && child.tag_name().name() == "entry")
+            .filter_map(alert_from_entry)
+            .collect()),
+        "alert" => Ok(alert_from_cap(root).into_iter().collect()),
+        other => Err(WeatherError::Parse(format!("unsupported feed <{}>", other))),
+    }
+}
+
+// ============================================================================
+// Alerts Feed
+// ============================================================================
+
+/// Client for the configured alerts feed
+#[derive(Debug, Clone)]
+pub struct AlertsFeed {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl AlertsFeed {
+    /// Client for the feed at `url`, or `None` if no feed is configured
+    pub fn new(url: &str) -> Option<Self> {
+        let url = url.trim();
+        if url.is_empty() {
+            return None;
+        }
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Some(Self {
+            client,
+            url: url.to_string(),
+        })
+    }
+
+    /// Feed URL with the location placeholders filled in
+    fn url_for(&self, location: Coordinates) -> String {
+        self.url
+            .replace("{latitude}", &format!("{:.4}", location.latitude))
+            .replace("{longitude}", &format!("{:.4}", location.longitude))
+    }
+
+    /// Fetch the alerts currently published for `location`
+    pub async fn fetch(&self, location: Coordinates) -> Result<Vec<WeatherAlert>, WeatherError> {
+        let response = self
+            .client
+            .get(self.url_for(location))
+            // Some services (api.weather.gov) reject requests without one
+            .header(reqwest::header::USER_AGENT, "cosmic-applet-timeplus")
+            .send()
+            .await
+            .map_err(|err| WeatherError::Request(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(WeatherError::Status(status.as_u16()));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|err| WeatherError::Request(err.to_string()))?;
+        parse_alerts(&body)
+    }
+}
+
+// ============================================================================
+// Notification Deduplication
+// ============================================================================
+
+/// IDs of alerts already notified, with when they may be forgotten
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotifiedAlerts {
+    /// Alert ID → forget after (Unix milliseconds)
+    alerts: BTreeMap<String, i64>,
+}
+
+impl NotifiedAlerts {
+    /// Active alerts not notified yet, which are recorded as notified
+    ///
+    /// IDs of alerts past their expiry (or a week old when they have none)
+    /// are dropped, keeping the persisted list short.
+    pub fn take_new(&mut self, alerts: &[WeatherAlert], now: DateTime<Utc>) -> Vec<WeatherAlert> {
+        let now_ms = now.timestamp_millis();
+        self.alerts.retain(|_, forget_after| *forget_after > now_ms);
+
+        let mut new = Vec::new();
+        for alert in alerts.iter().filter(|alert| alert.is_active(now)) {
+            if self.alerts.contains_key(&alert.id) {
+                continue;
+            }
+            let forget_after = alert.expires.unwrap_or(now + REMEMBER_WITHOUT_EXPIRY);
+            self.alerts
+                .insert(alert.id.clone(), forget_after.timestamp_millis());
+            new.push(alert.clone());
+        }
+        new
+    }
+}
+
+fn notified_path() -> Option<PathBuf> {
+    crate::paths::get_state_dir().map(|dir| dir.join(NOTIFIED_FILE))
+}
+
+/// Load the IDs of alerts notified by previous runs
+pub fn load_notified() -> NotifiedAlerts {
+    let Some(data) = notified_path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return NotifiedAlerts::default();
+    };
+    serde_json::from_str(&data).unwrap_or_else(|err| {
+        tracing::warn!("[Weather] Ignoring corrupt notified alerts: {}", err);
+        NotifiedAlerts::default()
+    })
+}
+
+/// Save the notified alert IDs, logging (not propagating) failures
+pub fn save_notified(notified: &NotifiedAlerts) {
+    let Some(path) = notified_path() else {
+        tracing::warn!("[Weather] No state directory available, notified alerts not saved");
+        return;
+    };
+
+    crate::paths::write_json(&path, notified, "[Weather] Failed to save notified alerts");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed-down Atom feed of api.weather.gov
+    const ATOM_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:cap="urn:oasis:names:tc:emergency:cap:1.2">
+  <id>https://api.weather.gov/alerts/active.atom?point=29.76,-95.37</id>
+  <title>Current watches, warnings, and advisories</title>
+  <entry>
+    <id>urn:oid:2.49.0.1.840.0.heat.001</id>
+    <title>Heat Advisory issued July 10 at 3:02PM CDT until July 11 at 8:00PM CDT</title>
+    <summary>Heat index values up to 110 expected.</summary>
+    <cap:event>Heat Advisory</cap:event>
+    <cap:severity>Moderate</cap:severity>
+    <cap:expires>2025-07-11T20:00:00-05:00</cap:expires>
+  </entry>
+  <entry>
+    <id>urn:oid:2.49.0.1.840.0.storm.002</id>
+    <title>Severe Thunderstorm Warning</title>
+    <cap:severity>Severe</cap:severity>
+  </entry>
+  <entry>
+    <title>Entry without an ID is skipped</title>
+  </entry>
+</feed>"#;
+
+    const CAP_MESSAGE: &str = r#"<alert xmlns="urn:oasis:names:tc:emergency:cap:1.2">
+  <identifier>2.49.0.0.276.0.DWD.PVW.frost</identifier>
+  <info>
+    <event>FROST</event>
+    <severity>Minor</severity>
+    <expires>2025-01-11T09:00:00+01:00</expires>
+    <headline>Official WARNING of FROST</headline>
+    <description>Frost between -3 °C and -8 °C is expected.</description>
+  </info>
+</alert>"#;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_parse_atom_feed() {
+        let alerts = parse_alerts(ATOM_FEED).unwrap();
+        assert_eq!(alerts.len(), 2);
+
+        assert_eq!(alerts[0].event, "Heat Advisory");
+        assert_eq!(alerts[0].severity, AlertSeverity::Moderate);
+        assert_eq!(alerts[0].expires, Some(at("2025-07-12T01:00:00Z")));
+        assert!(alerts[0].summary.starts_with("Heat index"));
+
+        // Missing fields fall back instead of dropping the alert
+        assert_eq!(alerts[1].event, "Severe Thunderstorm Warning");
+        assert_eq!(alerts[1].expires, None);
+    }
+
+    #[test]
+    fn test_parse_cap_message() {
+        let alerts = parse_alerts(CAP_MESSAGE).unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].id, "2.49.0.0.276.0.DWD.PVW.frost");
+        assert_eq!(alerts[0].title, "Official WARNING of FROST");
+        assert_eq!(alerts[0].severity, AlertSeverity::Minor);
+
+        assert!(matches!(
+            parse_alerts("<rss></rss>"),
+            Err(WeatherError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_alerts_are_notified_once() {
+        let alerts = parse_alerts(ATOM_FEED).unwrap();
+        let mut notified = NotifiedAlerts::default();
+
+        let now = at("2025-07-11T12:00:00Z");
+        assert_eq!(notified.take_new(&alerts, now).len(), 2);
+        assert!(notified.take_new(&alerts, now).is_empty());
+
+        // Survives a restart
+        let json = serde_json::to_string(&notified).unwrap();
+        let mut restored: NotifiedAlerts = serde_json::from_str(&json).unwrap();
+        assert!(restored.take_new(&alerts, now).is_empty());
+
+        // Expired alerts are neither notified nor remembered
+        let later = at("2025-07-13T12:00:00Z");
+        assert!(restored.take_new(&alerts, later).is_empty());
+        assert_eq!(restored.alerts.len(), 1, "only the alert without expiry");
+    }
+
+    #[test]
+    fn test_feed_url_placeholders() {
+        let feed = AlertsFeed::new(
+            " https://api.weather.gov/alerts/active.atom?point={latitude},{longitude} ",
+        )
+        .unwrap();
+        let location = Coordinates {
+            latitude: 29.7604,
+            longitude: -95.3698,
+        };
+        assert_eq!(
+            feed.url_for(location),
+            "https://api.weather.gov/alerts/active.atom?point=29.7604,-95.3698"
+        );
+        assert!(AlertsFeed::new("").is_none());
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Weather cache
+//!
+//! Keeps the last successful weather report in
+//! `$XDG_CACHE_HOME/cosmic-applet-timeplus` together with the time it was
+//! fetched, so the Weather tab has something to show right after login and
+//! while the network is down. The cache belongs to one location: a report
+//! saved for other coordinates is ignored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::weather_provider::{Coordinates, WeatherReport};
+
+/// File name of the cache inside the cache directory
+const CACHE_FILE: &str = "weather.json";
+
+// ============================================================================
+// Serialized Representation
+// ============================================================================
+
+/// Serde helpers for chrono dates (chrono is built without its serde feature)
+pub mod naive_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    const FORMAT: &str = "%Y-%m-%d";
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&date.format(FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveDate::parse_from_str(&text, FORMAT).map_err(D::Error::custom)
+    }
+}
+
+/// Serde helpers for chrono local date-times (see `naive_date`)
+pub mod naive_datetime {
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    const FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+    pub fn serialize<S: Serializer>(
+        datetime: &NaiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&datetime.format(FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NaiveDateTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveDateTime::parse_from_str(&text, FORMAT).map_err(D::Error::custom)
+    }
+}
+
+/// Persisted report with its provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WeatherCache {
+    /// When the report was fetched (Unix milliseconds)
+    fetched_at_ms: i64,
+    location: Coordinates,
+    report: WeatherReport,
+}
+
+// ============================================================================
+// Load / Save
+// ============================================================================
+
+fn cache_path() -> Option<PathBuf> {
+    crate::paths::get_cache_dir().map(|dir| dir.join(CACHE_FILE))
+}
+
+/// Load the cached report for `location` and when it was fetched
+///
+/// A missing, unreadable or foreign (other location) cache is not an error:
+/// the tab simply waits for the first fetch.
+pub fn load(location: Coordinates) -> Option<(WeatherReport, DateTime<Utc>)> {
+    let path = cache_path()?;
+    let data = std::fs::read_to_string(&path).ok()?;
+    let cache = match serde_json::from_str::<WeatherCache>(&data) {
+        Ok(cache) => cache,
+        Err(err) => {
+            tracing::warn!(
+                "[Weather] Ignoring corrupt cache {}: {}",
+                path.display(),
+                err
+            );
+            return None;
+        }
+    };
+
+    if cache.location != location {
+        tracing::debug!("[Weather] Cache is for {:?}, ignoring", cache.location);
+        return None;
+    }
+    let fetched_at = DateTime::from_timestamp_millis(cache.fetched_at_ms)?;
+    tracing::info!("[Weather] Restored report fetched at {}", fetched_at);
+    Some((cache.report, fetched_at))
+}
+
+/// Save the report, logging (not propagating) failures
+pub fn save(location: Coordinates, report: &WeatherReport, fetched_at: DateTime<Utc>) {
+    let Some(path) = cache_path() else {
+        tracing::warn!("[Weather] No cache directory available, report not cached");
+        return;
+    };
+
+    let cache = WeatherCache {
+        fetched_at_ms: fetched_at.timestamp_millis(),
+        location,
+        report: report.clone(),
+    };
+    crate::paths::write_json(&path, &cache, "[Weather] Failed to cache report");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_provider::{
+        CurrentConditions, DailyForecast, HourlyForecast, WeatherCondition,
+    };
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_report_roundtrip() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let cache = WeatherCache {
+            fetched_at_ms: 1_736_500_000_000,
+            location: Coordinates {
+                latitude: 52.52,
+                longitude: 13.41,
+            },
+            report: WeatherReport {
+                current: CurrentConditions {
+                    temperature: -2.5,
+                    feels_like: -6.0,
+                    condition: WeatherCondition::Snow,
+                    wind_speed: 18.0,
+                    wind_direction: 270.0,
+                    humidity: 90.0,
+                    pressure: Some(1021.0),
+                    is_day: false,
+                },
+                daily: vec![DailyForecast {
+                    date,
+                    condition: WeatherCondition::Snow,
+                    temperature_max: 0.5,
+                    temperature_min: -4.0,
+                    precipitation_probability: None,
+                    precipitation_sum: Some(3.2),
+                }],
+                hourly: vec![HourlyForecast {
+                    time: date.and_hms_opt(23, 0, 0).unwrap(),
+                    condition: WeatherCondition::Fog,
+                    temperature: -3.0,
+                    precipitation_probability: Some(20.0),
+                    is_day: false,
+                }],
+                utc_offset_seconds: Some(3600),
+            },
+        };
+
+        let json = serde_json::to_string(&cache).unwrap();
+        let restored: WeatherCache = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.report, cache.report);
+        assert!(json.contains("\"2025-01-10T23:00:00\""));
+    }
+}
@@ -0,0 +1,600 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Weather data sources
+//!
+//! The Weather tab only talks to the `WeatherProvider` trait. The bundled
+//! implementation speaks the Open-Meteo forecast API; its base URL comes from
+//! the config, so self-hosted instances (or a local stand-in during tests)
+//! can be used instead of the public service. Values are always requested in
+//! metric units and converted for display.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Public Open-Meteo instance, used unless `weather_base_url` is set
+pub const DEFAULT_BASE_URL: &str = "https://api.open-meteo.com";
+
+/// Give up on a request after this long (the panel must never hang on it)
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Days of daily forecast requested (today included)
+const FORECAST_DAYS: u8 = 7;
+
+/// Hours of hourly forecast requested, from the current hour
+const FORECAST_HOURS: u8 = 24;
+
+/// Timestamp format of Open-Meteo with `timezone=auto` (location local time)
+const OPEN_METEO_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+// ============================================================================
+// Weather Data
+// ============================================================================
+
+/// Geographic position in decimal degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Simplified sky condition, derived from WMO weather interpretation codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Overcast,
+    Fog,
+    Drizzle,
+    Rain,
+    FreezingRain,
+    Snow,
+    Showers,
+    Thunderstorm,
+    Unknown,
+}
+
+impl WeatherCondition {
+    /// Map a WMO weather code (as used by Open-Meteo) to a condition
+    pub fn from_wmo_code(code: u8) -> Self {
+        match code {
+            0 | 1 => WeatherCondition::Clear,
+            2 => WeatherCondition::PartlyCloudy,
+            3 => WeatherCondition::Overcast,
+            45 | 48 => WeatherCondition::Fog,
+            51..=55 => WeatherCondition::Drizzle,
+            56 | 57 | 66 | 67 => WeatherCondition::FreezingRain,
+            61..=65 => WeatherCondition::Rain,
+            71..=77 | 85 | 86 => WeatherCondition::Snow,
+            80..=82 => WeatherCondition::Showers,
+            95..=99 => WeatherCondition::Thunderstorm,
+            _ => WeatherCondition::Unknown,
+        }
+    }
+}
+
+/// Conditions at the time of the request, in metric units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrentConditions {
+    /// Air temperature at 2 m (°C)
+    pub temperature: f64,
+    /// Apparent ("feels like") temperature (°C)
+    pub feels_like: f64,
+    pub condition: WeatherCondition,
+    /// Wind speed at 10 m (km/h)
+    pub wind_speed: f64,
+    /// Direction the wind blows from, clockwise from north (degrees)
+    pub wind_direction: f64,
+    /// Relative humidity (%)
+    pub humidity: f64,
+    /// Pressure reduced to sea level (hPa), if reported
+    #[serde(default)]
+    pub pressure: Option<f64>,
+    /// Daylight at the location (selects day or night icons)
+    pub is_day: bool,
+}
+
+/// Forecast for one day at the location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyForecast {
+    #[serde(with = "crate::weather_cache::naive_date")]
+    pub date: NaiveDate,
+    /// Most significant condition of the day
+    pub condition: WeatherCondition,
+    /// Highest temperature (°C)
+    pub temperature_max: f64,
+    /// Lowest temperature (°C)
+    pub temperature_min: f64,
+    /// Highest chance of precipitation over the day (%), if known
+    pub precipitation_probability: Option<f64>,
+    /// Total precipitation over the day (mm), if known
+    #[serde(default)]
+    pub precipitation_sum: Option<f64>,
+}
+
+/// Forecast for one hour at the location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HourlyForecast {
+    /// Start of the hour, in the location's local time
+    #[serde(with = "crate::weather_cache::naive_datetime")]
+    pub time: NaiveDateTime,
+    pub condition: WeatherCondition,
+    /// Temperature (°C)
+    pub temperature: f64,
+    /// Chance of precipitation (%), if known
+    pub precipitation_probability: Option<f64>,
+    pub is_day: bool,
+}
+
+/// Everything a provider returns for a location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeatherReport {
+    pub current: CurrentConditions,
+    /// Upcoming days, today first
+    pub daily: Vec<DailyForecast>,
+    /// Upcoming hours, current hour first
+    pub hourly: Vec<HourlyForecast>,
+    /// Offset of the location's local time, which forecast dates and times
+    /// are in (seconds east of UTC; unknown in caches of older versions)
+    #[serde(default)]
+    pub utc_offset_seconds: Option<i32>,
+}
+
+impl WeatherReport {
+    /// `now` in the location's local time (the system's when unknown)
+    pub fn local_time(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self.utc_offset_seconds.and_then(FixedOffset::east_opt) {
+            Some(offset) => now.with_timezone(&offset).naive_local(),
+            None => now.with_timezone(&chrono::Local).naive_local(),
+        }
+    }
+
+    /// Daily forecasts from `today` on (a cached report may start earlier)
+    pub fn days_from(&self, today: NaiveDate) -> &[DailyForecast] {
+        let start = self.daily.partition_point(|day| day.date < today);
+        &self.daily[start..]
+    }
+
+    /// Hourly forecasts from the hour containing `now` on
+    pub fn hours_from(&self, now: NaiveDateTime) -> &[HourlyForecast] {
+        let start = self
+            .hourly
+            .partition_point(|hour| hour.time + TimeDelta::hours(1) <= now);
+        &self.hourly[start..]
+    }
+}
+
+/// Why weather data could not be fetched
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeatherError {
+    /// Connection failed or timed out (typically offline)
+    Request(String),
+    /// Server answered with an error status
+    Status(u16),
+    /// Response body is not what the provider expects
+    Parse(String),
+}
+
+impl std::fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeatherError::Request(err) => write!(f, "request failed: {}", err),
+            WeatherError::Status(status) => write!(f, "server returned HTTP {}", status),
+            WeatherError::Parse(err) => write!(f, "unexpected response: {}", err),
+        }
+    }
+}
+
+// ============================================================================
+// Provider Trait
+// ============================================================================
+
+/// Source of weather data for a location
+pub trait WeatherProvider {
+    /// Fetch the current conditions and forecasts at `location`
+    fn fetch(
+        &self,
+        location: Coordinates,
+    ) -> impl Future<Output = Result<WeatherReport, WeatherError>> + Send;
+}
+
+// ============================================================================
+// Open-Meteo
+// ============================================================================
+
+/// Client for the Open-Meteo forecast API (or any compatible server)
+#[derive(Debug, Clone)]
+pub struct OpenMeteo {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenMeteo {
+    /// Client for the server at `base_url` (e.g. `https://api.open-meteo.com`)
+    ///
+    /// An empty URL selects `DEFAULT_BASE_URL`.
+    pub fn new(base_url: &str) -> Self {
+        let base_url = match base_url.trim().trim_end_matches('/') {
+            "" => DEFAULT_BASE_URL,
+            url => url,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            base_url: base_url.to_string(),
+        }
+    }
+
+    fn forecast_url(&self) -> String {
+        format!("{}/v1/forecast", self.base_url)
+    }
+}
+
+/// `current` block of an Open-Meteo forecast response
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    relative_humidity_2m: f64,
+    weather_code: u8,
+    wind_speed_10m: f64,
+    wind_direction_10m: f64,
+    #[serde(default)]
+    pressure_msl: Option<f64>,
+    is_day: u8,
+}
+
+/// `daily` block: one array per variable, indexed like `time`
+#[derive(Debug, Default, Deserialize)]
+struct OpenMeteoDaily {
+    time: Vec<String>,
+    weather_code: Vec<Option<u8>>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_probability_max: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_sum: Vec<Option<f64>>,
+}
+
+/// `hourly` block: one array per variable, indexed like `time`
+#[derive(Debug, Default, Deserialize)]
+struct OpenMeteoHourly {
+    time: Vec<String>,
+    weather_code: Vec<Option<u8>>,
+    temperature_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_probability: Vec<Option<f64>>,
+    #[serde(default)]
+    is_day: Vec<Option<u8>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    #[serde(default)]
+    utc_offset_seconds: Option<i32>,
+    current: OpenMeteoCurrent,
+    #[serde(default)]
+    daily: OpenMeteoDaily,
+    #[serde(default)]
+    hourly: OpenMeteoHourly,
+}
+
+impl From<OpenMeteoCurrent> for CurrentConditions {
+    fn from(current: OpenMeteoCurrent) -> Self {
+        Self {
+            temperature: current.temperature_2m,
+            feels_like: current.apparent_temperature,
+            condition: WeatherCondition::from_wmo_code(current.weather_code),
+            wind_speed: current.wind_speed_10m,
+            wind_direction: current.wind_direction_10m,
+            humidity: current.relative_humidity_2m,
+            pressure: current.pressure_msl,
+            is_day: current.is_day != 0,
+        }
+    }
+}
+
+/// Value at `index` of a column that may be short or contain nulls
+fn column_value<T: Copy>(column: &[Option<T>], index: usize) -> Option<T> {
+    column.get(index).copied().flatten()
+}
+
+impl OpenMeteoDaily {
+    /// Days with a valid date and both temperatures (others are skipped)
+    fn forecasts(&self) -> Vec<DailyForecast> {
+        self.time
+            .iter()
+            .enumerate()
+            .filter_map(|(index, time)| {
+                Some(DailyForecast {
+                    date: NaiveDate::parse_from_str(time, "%Y-%m-%d").ok()?,
+                    condition: column_value(&self.weather_code, index)
+                        .map_or(WeatherCondition::Unknown, WeatherCondition::from_wmo_code),
+                    temperature_max: column_value(&self.temperature_2m_max, index)?,
+                    temperature_min: column_value(&self.temperature_2m_min, index)?,
+                    precipitation_probability: column_value(
+                        &self.precipitation_probability_max,
+                        index,
+                    ),
+                    precipitation_sum: column_value(&self.precipitation_sum, index),
+                })
+            })
+            .collect()
+    }
+}
+
+impl OpenMeteoHourly {
+    /// Hours with a valid time and temperature (others are skipped)
+    fn forecasts(&self) -> Vec<HourlyForecast> {
+        self.time
+            .iter()
+            .enumerate()
+            .filter_map(|(index, time)| {
+                Some(HourlyForecast {
+                    time: NaiveDateTime::parse_from_str(time, OPEN_METEO_TIME_FORMAT).ok()?,
+                    condition: column_value(&self.weather_code, index)
+                        .map_or(WeatherCondition::Unknown, WeatherCondition::from_wmo_code),
+                    temperature: column_value(&self.temperature_2m, index)?,
+                    precipitation_probability: column_value(&self.precipitation_probability, index),
+                    is_day: column_value(&self.is_day, index).is_none_or(|is_day| is_day != 0),
+                })
+            })
+            .collect()
+    }
+}
+
+impl From<OpenMeteoResponse> for WeatherReport {
+    fn from(response: OpenMeteoResponse) -> Self {
+        Self {
+            daily: response.daily.forecasts(),
+            hourly: response.hourly.forecasts(),
+            current: response.current.into(),
+            utc_offset_seconds: response.utc_offset_seconds,
+        }
+    }
+}
+
+impl WeatherProvider for OpenMeteo {
+    async fn fetch(&self, location: Coordinates) -> Result<WeatherReport, WeatherError> {
+        let response = self
+            .client
+            .get(self.forecast_url())
+            .query(&[
+                ("latitude", location.latitude.to_string()),
+                ("longitude", location.longitude.to_string()),
+                (
+                    "current",
+                    "temperature_2m,apparent_temperature,relative_humidity_2m,\
+                     weather_code,wind_speed_10m,wind_direction_10m,pressure_msl,is_day"
+                        .to_string(),
+                ),
+                (
+                    "daily",
+                    "weather_code,temperature_2m_max,temperature_2m_min,\
+                     precipitation_probability_max,precipitation_sum"
+                        .to_string(),
+                ),
+                (
+                    "hourly",
+                    "temperature_2m,weather_code,precipitation_probability,is_day".to_string(),
+                ),
+                ("forecast_days", FORECAST_DAYS.to_string()),
+                ("forecast_hours", FORECAST_HOURS.to_string()),
+                // Dates and hours in the location's local time
+                ("timezone", "auto".to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|err| WeatherError::Request(err.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(WeatherError::Status(status.as_u16()));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|err| WeatherError::Request(err.to_string()))?;
+        serde_json::from_str::<OpenMeteoResponse>(&body)
+            .map(WeatherReport::from)
+            .map_err(|err| WeatherError::Parse(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Trimmed-down response of the real API
+    const FORECAST_RESPONSE: &str = r#"{
+        "latitude": -23.5,
+        "longitude": -46.625,
+        "utc_offset_seconds": -10800,
+        "current_units": {"temperature_2m": "°C"},
+        "current": {
+            "time": "2025-01-10T14:00",
+            "interval": 900,
+            "temperature_2m": 27.4,
+            "apparent_temperature": 29.1,
+            "relative_humidity_2m": 64,
+            "weather_code": 80,
+            "wind_speed_10m": 11.2,
+            "wind_direction_10m": 135,
+            "pressure_msl": 1012.4,
+            "is_day": 1
+        },
+        "daily": {
+            "time": ["2025-01-10", "2025-01-11", "2025-01-12"],
+            "weather_code": [80, 3, null],
+            "temperature_2m_max": [30.2, 26.0, null],
+            "temperature_2m_min": [19.8, 18.1, null],
+            "precipitation_probability_max": [85, null, null],
+            "precipitation_sum": [12.5, 0.0, null]
+        },
+        "hourly": {
+            "time": ["2025-01-10T14:00", "2025-01-10T15:00"],
+            "temperature_2m": [27.4, 26.9],
+            "weather_code": [80, 95],
+            "precipitation_probability": [70, 90],
+            "is_day": [1, 1]
+        }
+    }"#;
+
+    /// Serve a single HTTP response on a local port, returning the base URL
+    /// and the request line received
+    async fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let read = socket.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..read]).into_owned();
+            request.lines().next().unwrap_or_default().to_string()
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_wmo_codes() {
+        assert_eq!(WeatherCondition::from_wmo_code(0), WeatherCondition::Clear);
+        assert_eq!(WeatherCondition::from_wmo_code(48), WeatherCondition::Fog);
+        assert_eq!(
+            WeatherCondition::from_wmo_code(66),
+            WeatherCondition::FreezingRain
+        );
+        assert_eq!(WeatherCondition::from_wmo_code(86), WeatherCondition::Snow);
+        assert_eq!(
+            WeatherCondition::from_wmo_code(99),
+            WeatherCondition::Thunderstorm
+        );
+        assert_eq!(
+            WeatherCondition::from_wmo_code(42),
+            WeatherCondition::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_meteo_against_local_server() {
+        let (base_url, server) = serve_once("200 OK", FORECAST_RESPONSE).await;
+        let location = Coordinates {
+            latitude: -23.55,
+            longitude: -46.63,
+        };
+
+        let report = OpenMeteo::new(&format!("{base_url}/"))
+            .fetch(location)
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /v1/forecast?latitude=-23.55&longitude=-46.63"));
+        assert_eq!(report.current.temperature, 27.4);
+        assert_eq!(report.current.condition, WeatherCondition::Showers);
+        assert_eq!(report.current.humidity, 64.0);
+        assert_eq!(report.current.pressure, Some(1012.4));
+        assert!(report.current.is_day);
+
+        // The day with null values is dropped, a null probability is kept as unknown
+        assert_eq!(report.daily.len(), 2);
+        assert_eq!(report.daily[0].precipitation_probability, Some(85.0));
+        assert_eq!(report.daily[0].precipitation_sum, Some(12.5));
+        assert_eq!(
+            report.daily[1].date,
+            NaiveDate::from_ymd_opt(2025, 1, 11).unwrap()
+        );
+        assert_eq!(report.daily[1].precipitation_probability, None);
+
+        assert_eq!(report.hourly.len(), 2);
+        assert_eq!(report.hourly[1].condition, WeatherCondition::Thunderstorm);
+        assert_eq!(report.hourly[1].time.format("%H:%M").to_string(), "15:00");
+        assert_eq!(report.utc_offset_seconds, Some(-10800));
+    }
+
+    #[test]
+    fn test_cached_report_skips_the_past() {
+        let day = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        let hour = |day, hour| {
+            NaiveDate::from_ymd_opt(2025, 1, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+        let daily = |date| DailyForecast {
+            date,
+            condition: WeatherCondition::Clear,
+            temperature_max: 20.0,
+            temperature_min: 10.0,
+            precipitation_probability: None,
+            precipitation_sum: None,
+        };
+        let hourly = |time| HourlyForecast {
+            time,
+            condition: WeatherCondition::Clear,
+            temperature: 15.0,
+            precipitation_probability: None,
+            is_day: true,
+        };
+        let report = WeatherReport {
+            current: CurrentConditions {
+                temperature: 15.0,
+                feels_like: 15.0,
+                condition: WeatherCondition::Clear,
+                wind_speed: 0.0,
+                wind_direction: 0.0,
+                humidity: 50.0,
+                pressure: None,
+                is_day: true,
+            },
+            daily: vec![daily(day(10)), daily(day(11)), daily(day(12))],
+            hourly: vec![
+                hourly(hour(10, 22)),
+                hourly(hour(10, 23)),
+                hourly(hour(11, 0)),
+            ],
+            utc_offset_seconds: Some(-10800),
+        };
+
+        // Fetched on the 10th, shown on the 11th at 00:30 local (03:30 UTC)
+        let now = report.local_time(
+            DateTime::parse_from_rfc3339("2025-01-11T03:30:00Z")
+                .unwrap()
+                .to_utc(),
+        );
+        assert_eq!(now, hour(11, 0) + TimeDelta::minutes(30));
+        assert_eq!(report.days_from(now.date())[0].date, day(11));
+        assert_eq!(report.days_from(now.date()).len(), 2);
+        assert_eq!(report.hours_from(now).len(), 1, "current hour kept");
+        assert!(report.hours_from(hour(11, 1)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_open_meteo_error_status() {
+        let (base_url, _server) = serve_once("503 Service Unavailable", "{}").await;
+        let location = Coordinates {
+            latitude: 0.0,
+            longitude: 0.0,
+        };
+
+        assert_eq!(
+            OpenMeteo::new(&base_url).fetch(location).await,
+            Err(WeatherError::Status(503))
+        );
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use cosmic::widget::Id;
 use cosmic::widget::segmented_button;
 use cosmic::{
@@ -15,21 +15,41 @@ use cosmic::{
     },
     widget::{autosize, button, icon, rectangle_tracker::*},
 };
+use std::collections::HashMap;
 use std::sync::LazyLock;
 use tokio::sync::watch;
 
+use crate::alarm::{Alarm, AlarmForm, AlarmRepeat, AlarmSchedule};
+use crate::astronomy::SunTimes;
+use crate::audio::AlarmSound;
+use crate::caldav::SyncStatus;
+use crate::calendar::CalendarMessage;
 use crate::config::TimeAppletConfig;
+use crate::events::EventSource;
+use crate::fl;
+use crate::location::Place;
+use crate::pomodoro::PomodoroSettings;
+use crate::stopwatch::StopwatchMessage;
+use crate::timer::{TimerEvent, TimerMessage};
+use crate::units::Units;
+use crate::weather::WeatherMessage;
+use crate::weather_alerts::{AlertSeverity, AlertsFeed, NotifiedAlerts};
+use crate::weather_provider::{OpenMeteo, WeatherProvider};
 use cosmic::applet::token::subscription::{
     TokenRequest, TokenUpdate, activation_token_subscription,
 };
+use cosmic_config::CosmicConfigEntry;
 use icu::locale::Locale;
 
 // Import global types from lib.rs (Neutral Messenger)
-use crate::{Message, Tab};
+use crate::{Message, NotificationSource, Tab};
 
 // Import localization function
 use crate::localize::get_system_locale;
 
+/// Length of an alarm snooze requested from a notification (minutes)
+const ALARM_SNOOZE_MINUTES: i64 = 5;
+
 static AUTOSIZE_MAIN_ID: LazyLock<Id> = LazyLock::new(|| Id::new("autosize-main"));
 
 pub struct Window {
@@ -38,10 +58,26 @@ pub struct Window {
     now: chrono::DateTime<chrono::FixedOffset>,
     timezone: Option<chrono_tz::Tz>,
     calendar_state: crate::calendar::CalendarState,
+    timer_state: crate::timer::TimerState,
+    weather_state: crate::weather::WeatherState,
+    /// Weather location derived from the system timezone (used when none is configured)
+    timezone_place: Option<Place>,
+    /// Weather alerts already notified (persisted across restarts)
+    notified_alerts: NotifiedAlerts,
     panel_formatter: crate::time::PanelFormatter, // Panel time formatter
     rectangle_tracker: Option<RectangleTracker<u32>>,
     rectangle: Rectangle,
     token_tx: Option<calloop::channel::Sender<TokenRequest>>,
+    /// Notifications currently on screen, by server-assigned ID
+    notifications: HashMap<u32, NotificationSource>,
+    /// New alarm form of the Timer tab
+    alarm_form: AlarmForm,
+    /// When each wall-clock alarm rings next
+    alarm_schedule: AlarmSchedule,
+    /// Handle used to write settings changed from the popup (alarms)
+    config_handle: Option<cosmic_config::Config>,
+    /// Alarm sounds currently playing (dropping one stops it)
+    alarm_sounds: HashMap<NotificationSource, crate::audio::Playback>,
     config: TimeAppletConfig,
     show_seconds_tx: watch::Sender<bool>,
     locale: Locale,
@@ -52,6 +88,378 @@ pub struct Window {
     panel_anchor: PanelAnchor,
 }
 
+impl Window {
+    /// Tick every second while the clock shows seconds or a countdown is running
+    fn sync_tick_rate(&self) {
+        let per_second =
+            self.config.should_show_seconds() || self.timer_state.needs_per_second_ticks();
+
+        // Don't interrupt the tick subscription unless necessary
+        self.show_seconds_tx.send_if_modified(|show_seconds| {
+            if *show_seconds != per_second {
+                *show_seconds = per_second;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Persist the timer session and raise one notification per timer event
+    ///
+    /// With `ring`, the configured alarm sound is played as well.
+    fn announce_timer_events(&mut self, events: Vec<TimerEvent>, ring: bool) -> app::Task<Message> {
+        if events.is_empty() {
+            return Task::none();
+        }
+
+        crate::session::save(&self.timer_state);
+        self.sync_tick_rate();
+        let tasks: Vec<_> = events
+            .into_iter()
+            .map(|event| {
+                tracing::info!("[Timer] {:?}", event);
+                self.notify(event.notification_source(), event.notification_body(), ring)
+            })
+            .collect();
+        Task::batch(tasks)
+    }
+
+    /// Fire the alarms due now and notify them
+    ///
+    /// Missed alarms (e.g. due while the system was asleep) are notified
+    /// silently; one-shot alarms are disabled in the config once they rang.
+    fn check_alarms(&mut self) -> app::Task<Message> {
+        let now = chrono::Utc::now();
+        let events = match self.timezone {
+            Some(tz) => self
+                .alarm_schedule
+                .due(&self.config.alarms, &now.with_timezone(&tz)),
+            None => self
+                .alarm_schedule
+                .due(&self.config.alarms, &now.with_timezone(&chrono::Local)),
+        };
+        if events.is_empty() {
+            return Task::none();
+        }
+
+        let mut tasks = Vec::new();
+        let mut finished_once = Vec::new();
+        for event in events {
+            let Some(alarm) = self.config.alarms.iter().find(|a| a.id == event.id) else {
+                continue;
+            };
+            tracing::info!("[Alarm] {:?} ({})", event, alarm.time_label());
+
+            let body = if event.missed {
+                fl!(
+                    "alarm-missed-body",
+                    label = alarm.display_label(),
+                    time = alarm.time_label()
+                )
+            } else {
+                fl!(
+                    "alarm-body",
+                    label = alarm.display_label(),
+                    time = alarm.time_label()
+                )
+            };
+            if alarm.repeat == AlarmRepeat::Once {
+                finished_once.push(alarm.id);
+            }
+            tasks.push(self.notify(NotificationSource::Alarm(event.id), body, !event.missed));
+        }
+
+        if !finished_once.is_empty() {
+            let mut alarms = self.config.alarms.clone();
+            for alarm in alarms.iter_mut().filter(|a| finished_once.contains(&a.id)) {
+                alarm.enabled = false;
+            }
+            self.save_alarms(alarms);
+        }
+        Task::batch(tasks)
+    }
+
+    /// Recompute when each alarm rings next, in the current timezone
+    fn reschedule_alarms(&mut self) {
+        let now = chrono::Utc::now();
+        match self.timezone {
+            Some(tz) => self
+                .alarm_schedule
+                .reschedule(&self.config.alarms, &now.with_timezone(&tz)),
+            None => self
+                .alarm_schedule
+                .reschedule(&self.config.alarms, &now.with_timezone(&chrono::Local)),
+        }
+    }
+
+    /// ID for a new alarm
+    fn next_alarm_id(&self) -> u32 {
+        crate::alarm::next_alarm_id(&self.config.alarms, self.config.next_alarm_id)
+    }
+
+    /// Persist the alarm list (and the ID counter) to cosmic-config and
+    /// reschedule
+    fn save_alarms(&mut self, alarms: Vec<Alarm>) {
+        let next_id = crate::alarm::next_alarm_id(&alarms, self.next_alarm_id());
+        match self.config_handle.as_ref() {
+            Some(handle) => {
+                if let Err(err) = self.config.set_next_alarm_id(handle, next_id) {
+                    tracing::error!(?err, "[Alarm] Failed to save the alarm ID counter");
+                }
+                if let Err(err) = self.config.set_alarms(handle, alarms) {
+                    tracing::error!(?err, "[Alarm] Failed to save alarms");
+                }
+            }
+            None => {
+                tracing::warn!("[Alarm] No config handle, alarms will not persist");
+                self.config.next_alarm_id = next_id;
+                self.config.alarms = alarms;
+            }
+        }
+        self.reschedule_alarms();
+    }
+
+    /// Where the weather is fetched for: the configured location, else the
+    /// city of the system timezone
+    fn weather_location(&self) -> Option<Place> {
+        crate::location::configured(&self.config).or_else(|| self.timezone_place.clone())
+    }
+
+    /// Sun times on `date` at the weather location, in the display timezone
+    fn sun_times(&self, date: NaiveDate) -> Option<SunTimes> {
+        let coordinates = self.weather_location()?.coordinates;
+        Some(match self.timezone {
+            Some(tz) => SunTimes::compute(date, coordinates, &tz),
+            None => SunTimes::compute(date, coordinates, &chrono::Local),
+        })
+    }
+
+    /// Read the configured ICS files again
+    fn load_events(&self) -> app::Task<Message> {
+        let paths = self.config.calendar_ics_paths.clone();
+        Task::perform(crate::events::load(paths), |events| {
+            cosmic::Action::App(Message::Calendar(CalendarMessage::EventsLoaded(events)))
+        })
+    }
+
+    /// Start over with the cached events of the configured CalDAV server,
+    /// then sync
+    fn reset_caldav(&mut self) -> app::Task<Message> {
+        let url = self.config.caldav_url.trim();
+        let events = if url.is_empty() {
+            Vec::new()
+        } else {
+            crate::caldav::load_cache(url).events()
+        };
+        self.calendar_state.caldav = SyncStatus::default();
+        self.calendar_state
+            .agenda
+            .set_events(EventSource::CalDav, events);
+        self.sync_caldav()
+    }
+
+    /// Sync the CalDAV calendars if a server is configured and a sync is due
+    fn sync_caldav(&mut self) -> app::Task<Message> {
+        let url = self.config.caldav_url.trim().to_string();
+        if url.is_empty() || !self.calendar_state.caldav.is_due(self.now.to_utc()) {
+            return Task::none();
+        }
+
+        tracing::debug!("[CalDAV] Syncing {}", url);
+        self.calendar_state.caldav.begin(self.now.to_utc());
+        let username = self.config.caldav_username.trim().to_string();
+        let sync = crate::caldav::refresh(url.clone(), username);
+        Task::perform(sync, move |result| {
+            cosmic::Action::App(Message::Calendar(CalendarMessage::CalDavSynced(
+                url, result,
+            )))
+        })
+    }
+
+    /// Region whose holidays are shown: the configured one, else the
+    /// locale's (none for a locale without region)
+    fn holiday_region(&self) -> String {
+        match self.config.holiday_region.trim() {
+            "" => self
+                .locale
+                .id
+                .region
+                .map(|region| region.as_str().to_string())
+                .unwrap_or_default(),
+            region => region.to_string(),
+        }
+    }
+
+    /// Expand the events and compute the holidays for the dates the calendar
+    /// grid shows, and expand the events of the agenda's upcoming days
+    fn refresh_agenda(&mut self) {
+        let (first, last) = crate::calendar::visible_range(
+            self.calendar_state.date_selected,
+            self.config.first_day_of_week,
+        );
+        self.calendar_state.holidays =
+            crate::holidays::between(&self.holiday_region(), first, last);
+        let today = self.calendar_state.date_today;
+        let days = self.config.agenda_days;
+        let agenda = &mut self.calendar_state.agenda;
+        match self.timezone {
+            Some(tz) => {
+                agenda.expand(first, last, &tz);
+                agenda.expand_upcoming(today, days, &tz);
+            }
+            None => {
+                agenda.expand(first, last, &chrono::Local);
+                agenda.expand_upcoming(today, days, &chrono::Local);
+            }
+        }
+    }
+
+    /// Persist the weather location picked in the Weather tab (`None` follows
+    /// the system timezone again)
+    fn save_weather_location(&mut self, place: Option<Place>) {
+        let (latitude, longitude, name) = match place {
+            Some(place) => (
+                Some(place.coordinates.latitude),
+                Some(place.coordinates.longitude),
+                place.name,
+            ),
+            None => (None, None, String::new()),
+        };
+        match self.config_handle.as_ref() {
+            Some(handle) => {
+                let result = self
+                    .config
+                    .set_weather_latitude(handle, latitude)
+                    .and_then(|_| self.config.set_weather_longitude(handle, longitude))
+                    .and_then(|_| self.config.set_weather_location_name(handle, name));
+                if let Err(err) = result {
+                    tracing::error!(?err, "[Weather] Failed to save location");
+                }
+            }
+            None => {
+                tracing::warn!("[Weather] No config handle, location will not persist");
+                self.config.weather_latitude = latitude;
+                self.config.weather_longitude = longitude;
+                self.config.weather_location_name = name;
+            }
+        }
+    }
+
+    /// Start over with the cached report of the current location, then fetch
+    fn reset_weather(&mut self) -> app::Task<Message> {
+        // Data of the previous location must not be shown for the new one
+        self.weather_state = cached_weather(self.weather_location().as_ref());
+        self.refresh_weather()
+    }
+
+    /// Fetch current weather conditions (and alerts, if a feed is configured)
+    /// for the weather location
+    ///
+    /// Does nothing without a location or while a request is in flight.
+    fn refresh_weather(&mut self) -> app::Task<Message> {
+        let Some(location) = self.weather_location().map(|place| place.coordinates) else {
+            return Task::none();
+        };
+        if self.weather_state.is_fetching() {
+            return Task::none();
+        }
+
+        tracing::debug!(
+            "[Weather] Fetching {:?} from {}",
+            location,
+            self.config.weather_base_url
+        );
+        self.weather_state.begin_fetch(self.now.to_utc());
+        let provider = OpenMeteo::new(&self.config.weather_base_url);
+        let report = Task::perform(
+            async move { provider.fetch(location).await },
+            move |result| {
+                cosmic::Action::App(Message::Weather(WeatherMessage::Fetched(location, result)))
+            },
+        );
+        let alerts = match AlertsFeed::new(&self.config.weather_alerts_url) {
+            Some(feed) => Task::perform(async move { feed.fetch(location).await }, move |result| {
+                cosmic::Action::App(Message::Weather(WeatherMessage::AlertsFetched(
+                    location, result,
+                )))
+            }),
+            None => Task::none(),
+        };
+        Task::batch([report, alerts])
+    }
+
+    /// Notify the alerts in effect that were not notified before (by this or
+    /// a previous run)
+    fn announce_weather_alerts(&mut self) -> app::Task<Message> {
+        let previous = self.notified_alerts.clone();
+        let new = self
+            .notified_alerts
+            .take_new(&self.weather_state.alerts, self.now.to_utc());
+        if self.notified_alerts != previous {
+            crate::weather_alerts::save_notified(&self.notified_alerts);
+        }
+
+        let mut tasks = Vec::new();
+        for alert in new {
+            tracing::info!("[Weather] New alert {}: {}", alert.id, alert.event);
+            // Dangerous weather stays on screen until dismissed
+            let severe = alert.severity >= AlertSeverity::Severe;
+            tasks.push(self.update(Message::TriggerNotification {
+                message: alert.title,
+                duration_secs: if severe { 0 } else { 10 },
+                source: NotificationSource::WeatherAlert,
+            }));
+        }
+        Task::batch(tasks)
+    }
+
+    /// Raise a notification for `source`, playing its alarm sound if `ring`
+    ///
+    /// Looping sounds get a notification that stays until dismissed, which
+    /// stops them.
+    fn notify(
+        &mut self,
+        source: NotificationSource,
+        message: String,
+        ring: bool,
+    ) -> app::Task<Message> {
+        let sound = AlarmSound::for_source(source, &self.config).filter(|_| ring);
+        let looping = sound.as_ref().is_some_and(AlarmSound::is_looping);
+
+        let notify = cosmic::Application::update(
+            self,
+            Message::TriggerNotification {
+                message,
+                duration_secs: if looping { 0 } else { 10 },
+                source,
+            },
+        );
+        match sound {
+            Some(sound) => Task::batch([notify, self.play_alarm(source, sound)]),
+            None => notify,
+        }
+    }
+
+    /// Play an alarm sound, replacing the one already ringing for `source`
+    fn play_alarm(&mut self, source: NotificationSource, sound: AlarmSound) -> app::Task<Message> {
+        tracing::debug!("[Audio] Playing {:?} alarm: {:?}", source, sound);
+        let (playback, future) = crate::audio::play(sound);
+        self.alarm_sounds.insert(source, playback);
+
+        Task::perform(future, |result| {
+            if let Err(err) = result {
+                // The notification has been sent already: it becomes a silent alarm
+                tracing::warn!(
+                    "[Audio] Alarm sound unavailable ({}), notification only",
+                    err
+                );
+            }
+            cosmic::Action::None
+        })
+    }
+}
+
 impl cosmic::Application for Window {
     type Message = Message;
     type Executor = cosmic::SingleThreadExecutor;
@@ -105,26 +513,50 @@ impl cosmic::Application for Window {
             config.format_strftime
         );
 
-        (
-            Self {
-                core,
-                popup: None,
-                now,
-                timezone: None,
-                calendar_state: crate::calendar::CalendarState::new(now),
-                panel_formatter: crate::time::PanelFormatter::new(locale.clone()),
-                rectangle_tracker: None,
-                rectangle: Rectangle::default(),
-                token_tx: None,
-                config,
-                show_seconds_tx,
-                locale,
-                selected_tab: Tab::Calendar,
-                tab_model,
-                panel_anchor,
-            },
-            Task::none(),
-        )
+        // Restore timers from the previous session and settle those that
+        // expired while the applet was not running
+        let mut timer_state = crate::session::load().unwrap_or_default();
+        let expired = timer_state.catch_up(&PomodoroSettings::from_config(&config), now.to_utc());
+
+        let config_handle = cosmic_config::Config::new(Self::APP_ID, TimeAppletConfig::VERSION)
+            .map_err(|err| tracing::error!(?err, "[Init] Failed to open config for writing"))
+            .ok();
+
+        let mut window = Self {
+            core,
+            popup: None,
+            now,
+            timezone: None,
+            calendar_state: crate::calendar::CalendarState::new(now),
+            timer_state,
+            weather_state: cached_weather(crate::location::configured(&config).as_ref()),
+            timezone_place: None,
+            notified_alerts: crate::weather_alerts::load_notified(),
+            panel_formatter: crate::time::PanelFormatter::new(locale.clone()),
+            rectangle_tracker: None,
+            rectangle: Rectangle::default(),
+            token_tx: None,
+            notifications: HashMap::new(),
+            alarm_form: AlarmForm::default(),
+            alarm_schedule: AlarmSchedule::default(),
+            config_handle,
+            alarm_sounds: HashMap::new(),
+            config,
+            show_seconds_tx,
+            locale,
+            selected_tab: Tab::Calendar,
+            tab_model,
+            panel_anchor,
+        };
+        window.sync_tick_rate();
+        window.reschedule_alarms();
+        // No sound for timers that expired while the applet was down
+        let timers = window.announce_timer_events(expired, false);
+        let weather = window.refresh_weather();
+        let events = window.load_events();
+        let caldav = window.reset_caldav();
+
+        (window, Task::batch([timers, weather, events, caldav]))
     }
 
     fn core(&self) -> &cosmic::app::Core {
@@ -147,6 +579,7 @@ impl cosmic::Application for Window {
             activation_token_subscription(0).map(Message::Token),
             crate::subscriptions::timezone_subscription(),
             crate::subscriptions::wake_from_sleep_subscription(),
+            crate::subscriptions::notification_subscription(),
             self.core.watch_config(Self::APP_ID).map(|u| {
                 for err in u.errors {
                     tracing::error!(?err, "Error watching config");
@@ -165,6 +598,7 @@ impl cosmic::Application for Window {
                 } else {
                     tracing::info!("[UI] Opening popup");
                     self.calendar_state.reset_to_today(self.now);
+                    self.refresh_agenda();
 
                     let new_id = window::Id::unique();
                     self.popup = Some(new_id);
@@ -191,7 +625,8 @@ impl cosmic::Application for Window {
 
                     popup_settings.positioner.size = None;
 
-                    get_popup(popup_settings)
+                    // Pick up edits of the ICS files
+                    Task::batch([get_popup(popup_settings), self.load_events()])
                 }
             }
             Message::Tick => {
@@ -199,7 +634,17 @@ impl cosmic::Application for Window {
                     || chrono::Local::now().into(),
                     |tz| chrono::Local::now().with_timezone(&tz).fixed_offset(),
                 );
-                Task::none()
+
+                let settings = PomodoroSettings::from_config(&self.config);
+                let events = self.timer_state.tick(&settings, self.now.to_utc());
+                let timers = self.announce_timer_events(events, true);
+                let weather = if self.weather_state.is_due(self.now.to_utc()) {
+                    self.refresh_weather()
+                } else {
+                    Task::none()
+                };
+                let caldav = self.sync_caldav();
+                Task::batch([timers, self.check_alarms(), weather, caldav])
             }
             Message::Rectangle(u) => {
                 match u {
@@ -220,6 +665,15 @@ impl cosmic::Application for Window {
                 Task::none()
             }
             Message::Calendar(msg) => {
+                // A sync started before the server changed must not replace
+                // the new server's events
+                if let crate::calendar::CalendarMessage::CalDavSynced(url, _) = &msg
+                    && url.as_str() != self.config.caldav_url.trim()
+                {
+                    tracing::debug!("[CalDAV] Dropping result for previous server {}", url);
+                    return Task::none();
+                }
+
                 // Log with full date context
                 match &msg {
                     crate::calendar::CalendarMessage::SelectDay(day) => {
@@ -238,10 +692,141 @@ impl cosmic::Application for Window {
                     crate::calendar::CalendarMessage::NextMonth => {
                         tracing::debug!("[Calendar] NextMonth");
                     }
+                    crate::calendar::CalendarMessage::ShowYear => {
+                        tracing::debug!(
+                            "[Calendar] ShowYear({})",
+                            self.calendar_state.date_selected.year()
+                        );
+                    }
+                    crate::calendar::CalendarMessage::ShowMonth(month) => {
+                        tracing::debug!("[Calendar] ShowMonth({})", month);
+                    }
+                    crate::calendar::CalendarMessage::PreviousYear => {
+                        tracing::debug!("[Calendar] PreviousYear");
+                    }
+                    crate::calendar::CalendarMessage::NextYear => {
+                        tracing::debug!("[Calendar] NextYear");
+                    }
+                    crate::calendar::CalendarMessage::JumpInput(_) => {}
+                    crate::calendar::CalendarMessage::JumpToDate => {
+                        tracing::debug!(
+                            "[Calendar] JumpToDate({:?})",
+                            self.calendar_state.jump_query
+                        );
+                    }
+                    crate::calendar::CalendarMessage::ShowAgenda => {
+                        tracing::debug!(
+                            "[Calendar] ShowAgenda({} upcoming)",
+                            self.calendar_state.agenda.upcoming().len()
+                        );
+                    }
+                    crate::calendar::CalendarMessage::CloseAgenda => {
+                        tracing::debug!("[Calendar] CloseAgenda");
+                    }
+                    crate::calendar::CalendarMessage::ToggleEventDetails(index) => {
+                        tracing::debug!("[Calendar] ToggleEventDetails({})", index);
+                    }
+                    crate::calendar::CalendarMessage::EventsLoaded(events) => {
+                        tracing::info!("[Calendar] Loaded {} events", events.len());
+                    }
+                    crate::calendar::CalendarMessage::CalDavSynced(_, Ok(events)) => {
+                        tracing::info!("[CalDAV] Synced {} events", events.len());
+                    }
+                    crate::calendar::CalendarMessage::CalDavSynced(_, Err(err)) => {
+                        tracing::warn!("[CalDAV] Sync failed: {}", err);
+                    }
                 }
                 self.calendar_state.update(msg);
+                self.refresh_agenda();
                 Task::none()
             }
+            Message::Timer(msg) => {
+                tracing::debug!("[Timer] {:?}", msg);
+                if let TimerMessage::Alarm(msg) = msg {
+                    let next_id = self.next_alarm_id();
+                    if let Some(alarms) = self.alarm_form.update(msg, &self.config.alarms, next_id)
+                    {
+                        self.save_alarms(alarms);
+                    }
+                    return Task::none();
+                }
+                if msg.stops_sound() {
+                    self.alarm_sounds.clear();
+                }
+                let copy_laps = matches!(msg, TimerMessage::Stopwatch(StopwatchMessage::CopyLaps));
+                let settings = PomodoroSettings::from_config(&self.config);
+                let save = msg.changes_session();
+                self.timer_state.update(msg, &settings, self.now.to_utc());
+                if save {
+                    crate::session::save(&self.timer_state);
+                }
+                self.sync_tick_rate();
+
+                if copy_laps {
+                    tracing::info!("[Timer] Copying laps to clipboard");
+                    cosmic::iced::clipboard::write(self.timer_state.stopwatch.laps_text())
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Weather(msg) => {
+                // A request still in flight when the location changed must not
+                // be taken (and cached) as the new location's data
+                if let WeatherMessage::Fetched(location, _)
+                | WeatherMessage::AlertsFetched(location, _) = &msg
+                    && self.weather_location().map(|place| place.coordinates) != Some(*location)
+                {
+                    tracing::debug!(
+                        "[Weather] Dropping result for previous location {:?}",
+                        location
+                    );
+                    return Task::none();
+                }
+                match &msg {
+                    WeatherMessage::Fetched(_, Err(err)) => {
+                        tracing::warn!("[Weather] Fetch failed: {}", err);
+                    }
+                    WeatherMessage::AlertsFetched(_, Err(err)) => {
+                        tracing::warn!("[Weather] Alerts fetch failed: {}", err);
+                    }
+                    _ => {}
+                }
+                let fetched = matches!(msg, WeatherMessage::Fetched(_, Ok(_)));
+                let alerts_fetched = matches!(msg, WeatherMessage::AlertsFetched(_, Ok(_)));
+                let refresh = matches!(msg, WeatherMessage::Refresh);
+                let location_change = match &msg {
+                    WeatherMessage::PickPlace(place) => Some(Some(place.clone())),
+                    WeatherMessage::UseTimezoneLocation => Some(None),
+                    _ => None,
+                };
+                self.weather_state.update(msg);
+
+                if let Some(place) = location_change {
+                    tracing::info!("[Weather] Location set to {:?}", place);
+                    let previous = self.weather_location();
+                    self.save_weather_location(place);
+                    if self.weather_location() != previous {
+                        return self.reset_weather();
+                    }
+                }
+                if fetched
+                    && let (Some(location), Some(report), Some(fetched_at)) = (
+                        self.weather_location().map(|place| place.coordinates),
+                        &self.weather_state.report,
+                        self.weather_state.fetched_at,
+                    )
+                {
+                    crate::weather_cache::save(location, report, fetched_at);
+                }
+                if alerts_fetched {
+                    return self.announce_weather_alerts();
+                }
+                if refresh {
+                    self.refresh_weather()
+                } else {
+                    Task::none()
+                }
+            }
             Message::OpenDateTimeSettings => {
                 let exec = "cosmic-settings time".to_string();
                 if let Some(tx) = self.token_tx.as_ref() {
@@ -278,40 +863,124 @@ impl cosmic::Application for Window {
                 Task::none()
             }
             Message::ConfigChanged(c) => {
-                // Don't interrupt the tick subscription unless necessary
-                self.show_seconds_tx.send_if_modified(|show_seconds| {
-                    let new_value = c.should_show_seconds();
-                    if *show_seconds != new_value {
-                        *show_seconds = new_value;
-                        true
-                    } else {
-                        false
-                    }
-                });
+                let alarms_changed = self.config.alarms != c.alarms;
+                let previous_location = self.weather_location();
+                let servers_changed = self.config.weather_base_url != c.weather_base_url
+                    || self.config.weather_alerts_url != c.weather_alerts_url;
+                let calendars_changed = self.config.calendar_ics_paths != c.calendar_ics_paths;
+                let caldav_changed = self.config.caldav_url != c.caldav_url
+                    || self.config.caldav_username != c.caldav_username;
                 self.config = c;
-                Task::none()
+                let weather_changed =
+                    servers_changed || self.weather_location() != previous_location;
+                self.sync_tick_rate();
+                if alarms_changed {
+                    self.reschedule_alarms();
+                }
+                let caldav = if caldav_changed {
+                    self.reset_caldav()
+                } else {
+                    Task::none()
+                };
+                // The first day of the week moves the visible dates
+                self.refresh_agenda();
+                let events = if calendars_changed {
+                    self.load_events()
+                } else {
+                    Task::none()
+                };
+                let weather = if weather_changed {
+                    self.reset_weather()
+                } else {
+                    Task::none()
+                };
+                Task::batch([events, caldav, weather])
             }
             Message::TimezoneUpdate(timezone) => {
                 let tz = crate::time::parse_timezone(&timezone);
                 self.now = chrono::Local::now().with_timezone(&tz).fixed_offset();
                 self.calendar_state.reset_to_today(self.now);
                 self.timezone = Some(tz);
+                // Ring what came due before the change, then move alarms: they
+                // ring at local time, so same wall-clock time, new instant
+                let tick = self.update(Message::Tick);
+                self.reschedule_alarms();
+                self.refresh_agenda();
 
-                self.update(Message::Tick)
+                let previous_location = self.weather_location();
+                self.timezone_place = crate::location::from_timezone(&timezone);
+                let weather = if self.weather_location() != previous_location {
+                    self.reset_weather()
+                } else {
+                    Task::none()
+                };
+                Task::batch([tick, weather])
             }
-            // Notification placeholders (Phase 3.9 - not yet implemented)
-            Message::TriggerNotification { .. } => {
-                // TODO: Implement in Phase 3.9
-                Task::none()
+            Message::WakeFromSleep => {
+                tracing::info!("[System] Resumed from sleep");
+                // Ring (as missed) what came due while asleep, then follow any
+                // wall-clock jump (DST, timezone) that happened in between.
+                // The weather report is refetched by this tick.
+                self.weather_state.expire();
+                let task = self.update(Message::Tick);
+                self.reschedule_alarms();
+                task
+            }
+            Message::TriggerNotification {
+                message,
+                duration_secs,
+                source,
+            } => {
+                tracing::info!("[Notification] {:?}: {}", source, message);
+                Task::perform(
+                    show_notification(source, message, duration_secs),
+                    move |result| match result {
+                        Ok(id) => cosmic::Action::App(Message::NotificationShown { id, source }),
+                        Err(err) => {
+                            tracing::warn!("[Notification] Failed to show notification: {}", err);
+                            cosmic::Action::None
+                        }
+                    },
+                )
             }
-            Message::NotificationDismissed => {
-                // TODO: Implement in Phase 3.9
+            Message::NotificationShown { id, source } => {
+                tracing::debug!("[Notification] Shown #{} ({:?})", id, source);
+                self.notifications.insert(id, source);
                 Task::none()
             }
-            Message::NotificationAction(_) => {
-                // TODO: Implement in Phase 3.9
+            Message::NotificationDismissed(id) => {
+                if let Some(source) = self.notifications.remove(&id) {
+                    tracing::debug!("[Notification] Closed #{}", id);
+                    self.alarm_sounds.remove(&source);
+                    if let NotificationSource::Alarm(alarm) = source {
+                        self.alarm_schedule.acknowledge(alarm);
+                    }
+                }
                 Task::none()
             }
+            Message::NotificationAction { id, action } => {
+                // Signals are broadcast: ignore notifications of other applications
+                let Some(source) = self.notifications.remove(&id) else {
+                    return Task::none();
+                };
+                tracing::info!("[Notification] Action '{}' on {:?}", action, source);
+                self.alarm_sounds.remove(&source);
+
+                if let NotificationSource::Alarm(alarm) = source {
+                    self.alarm_schedule.acknowledge(alarm);
+                    if action == NotificationSource::ACTION_SNOOZE {
+                        let at =
+                            chrono::Utc::now() + chrono::TimeDelta::minutes(ALARM_SNOOZE_MINUTES);
+                        self.alarm_schedule.snooze(alarm, at);
+                    }
+                    return Task::none();
+                }
+
+                match source.timer_message(&action) {
+                    Some(msg) => self.update(Message::Timer(msg)),
+                    None => Task::none(),
+                }
+            }
             Message::TabActivated(entity) => {
                 self.tab_model.activate(entity);
                 if let Some(tab) = self.tab_model.data::<Tab>(entity) {
@@ -327,12 +996,24 @@ impl cosmic::Application for Window {
         // Use pre-captured panel position (immutable during process lifecycle)
         let horizontal = matches!(self.panel_anchor, PanelAnchor::Top | PanelAnchor::Bottom);
 
+        // Outdated weather is left out: the panel has no room to flag it
+        let weather = self
+            .weather_state
+            .fresh_report(self.now.to_utc())
+            .map(|report| {
+                crate::panel::PanelWeather::new(
+                    &report.current,
+                    Units::resolve(&self.config, &self.locale),
+                )
+            });
         let panel_view = crate::panel::view(
             &self.panel_formatter,
             &self.now,
             &self.config,
             &self.core.applet,
             horizontal,
+            self.timer_state.panel_countdown(self.now.to_utc()),
+            weather.as_ref(),
         );
 
         let button = button::custom(panel_view)
@@ -358,7 +1039,16 @@ impl cosmic::Application for Window {
     fn view_window(&self, _id: window::Id) -> Element<'_, Message> {
         crate::popup::view(
             &self.locale,
-            &self.calendar_state,
+            crate::popup::TabStates {
+                calendar: &self.calendar_state,
+                timer: &self.timer_state,
+                alarm_form: &self.alarm_form,
+                alarm_schedule: &self.alarm_schedule,
+                weather: &self.weather_state,
+                timezone_place: self.timezone_place.as_ref(),
+                sun_today: self.sun_times(self.now.date_naive()),
+                sun_selected: self.sun_times(self.calendar_state.date_selected),
+            },
             &self.now,
             &self.config,
             self.selected_tab,
@@ -371,3 +1061,41 @@ impl cosmic::Application for Window {
         Some(Message::CloseRequested(id))
     }
 }
+
+/// Weather state for `location`, starting from its cached report
+fn cached_weather(location: Option<&Place>) -> crate::weather::WeatherState {
+    location
+        .and_then(|place| crate::weather_cache::load(place.coordinates))
+        .map(|(report, fetched_at)| crate::weather::WeatherState::restore(report, fetched_at))
+        .unwrap_or_default()
+}
+
+/// Send a desktop notification through the freedesktop notification server
+///
+/// Returns the server-assigned ID, used to route the action and close signals
+/// received by `notification_subscription` back to `source`.
+async fn show_notification(
+    source: NotificationSource,
+    body: String,
+    duration_secs: u64,
+) -> notify_rust::error::Result<u32> {
+    let timeout = if duration_secs == 0 {
+        notify_rust::Timeout::Never
+    } else {
+        notify_rust::Timeout::Milliseconds(
+            u32::try_from(duration_secs.saturating_mul(1000)).unwrap_or(u32::MAX),
+        )
+    };
+
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(&source.summary())
+        .body(&body)
+        .icon(source.icon_name())
+        .timeout(timeout);
+    for (key, label) in source.actions() {
+        notification.action(key, &label);
+    }
+
+    notification.show_async().await.map(|handle| handle.id())
+}